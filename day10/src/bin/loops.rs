@@ -0,0 +1,136 @@
+use day10::maze::Maze;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let maze = match Maze::parse(input) {
+        Ok(maze) => maze,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let start = maze.start();
+    println!(
+        "Start at ({}, {}) is {}",
+        start.x,
+        start.y,
+        maze.start_pipe().to_char()
+    );
+    for l in maze.loops() {
+        let first = l.tiles[0];
+        let marker = if l.tiles.contains(&start) {
+            " (main)"
+        } else {
+            ""
+        };
+        println!(
+            "Loop at ({}, {}): length {}{}",
+            first.x,
+            first.y,
+            l.len(),
+            marker
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day10::maze::{Direction, MazeError, Pipe};
+    use glam::IVec2;
+
+    #[test]
+    fn test_start_inferred() {
+        let maze = Maze::parse(include_str!("../../example1.txt")).unwrap();
+        assert_eq!(maze.start_pipe(), Pipe::BendSouthEast);
+        let maze = Maze::parse(include_str!("../../example2.txt")).unwrap();
+        assert_eq!(maze.start_pipe(), Pipe::BendSouthEast);
+        let maze = Maze::parse(include_str!("../../example3.txt")).unwrap();
+        assert_eq!(maze.start_pipe(), Pipe::BendSouthWest);
+    }
+
+    #[test]
+    fn test_start_ignores_dangling_neighbours() {
+        // The pipe north of S points at it but is not part of any loop
+        let input = ".|...\n.S-7.\n.|.|.\n.L-J.\n";
+        let maze = Maze::parse(input).unwrap();
+        assert_eq!(maze.start_pipe(), Pipe::BendSouthEast);
+        assert_eq!(maze.main_loop().len(), 8);
+    }
+
+    #[test]
+    fn test_ambiguous_start() {
+        // Two loops meet at the start tile, so it could be either bend
+        match Maze::parse("F7.\nLS7\n.LJ\n") {
+            Err(MazeError::AmbiguousStart { pos, candidates }) => {
+                assert_eq!(pos, IVec2::new(1, 1));
+                assert_eq!(candidates, vec![Pipe::BendNorthWest, Pipe::BendSouthEast]);
+            }
+            other => panic!("expected an ambiguous start, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_start() {
+        let maze = Maze::parse(".....\n.S-7.\n.|.|.\n.L-..\n.....\n");
+        match maze {
+            Err(MazeError::InconsistentStart { pos, connected }) => {
+                assert_eq!(pos, IVec2::new(1, 1));
+                assert_eq!(connected, vec![Direction::South, Direction::East]);
+            }
+            other => panic!("expected an inconsistent start, got {:?}", other),
+        }
+        assert_eq!(
+            Maze::parse("..\n.S\n").unwrap_err(),
+            MazeError::InconsistentStart {
+                pos: IVec2::new(1, 1),
+                connected: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Maze::parse("").unwrap_err(), MazeError::Empty);
+        assert_eq!(Maze::parse("..\n..\n").unwrap_err(), MazeError::NoStart);
+        assert_eq!(
+            Maze::parse("S.\n.S\n").unwrap_err(),
+            MazeError::MultipleStarts(vec![IVec2::new(0, 0), IVec2::new(1, 1)])
+        );
+        assert_eq!(
+            Maze::parse("S.\n.x\n").unwrap_err(),
+            MazeError::UnknownTile {
+                pos: IVec2::new(1, 1),
+                tile: 'x'
+            }
+        );
+        assert_eq!(
+            Maze::parse("S.\n.\n").unwrap_err(),
+            MazeError::Ragged { row: 1 }
+        );
+    }
+
+    #[test]
+    fn test_all_loops() {
+        let input = "F7.FS7\nLJ.|.|\n...L-J\n..F7..\n..LJ..\n";
+        let maze = Maze::parse(input).unwrap();
+        let lengths = maze.loops().iter().map(|l| l.len()).collect::<Vec<_>>();
+        assert_eq!(lengths, vec![4, 8, 4]);
+        assert!(maze.loops()[1].tiles.contains(&maze.start()));
+        assert_eq!(maze.start_pipe(), Pipe::Horizontal);
+    }
+
+    #[test]
+    fn test_loops_skip_open_paths() {
+        let maze = Maze::parse(include_str!("../../example3.txt")).unwrap();
+        let loops = maze.loops();
+        assert!(loops.iter().any(|l| l.tiles.contains(&maze.start())));
+        assert!(loops.iter().all(|l| l.len() % 2 == 0));
+        for l in &loops {
+            for &t in &l.tiles {
+                assert_eq!(maze.trace(t).map(|t| t.len()), Some(l.len()));
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use day10::maze::Maze;
+use day10::render::RenderStyle;
+
+// Usage: render [output file]
+// Without a path the loop is drawn in colour to the terminal, otherwise it is
+// written as plain text to the given file
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let maze = Maze::parse(input).unwrap();
+
+    match std::env::args().nth(1) {
+        Some(path) => {
+            std::fs::write(&path, maze.render(RenderStyle::Plain)).unwrap();
+            println!("Wrote {}", path);
+        }
+        None => print!("{}", maze.render(RenderStyle::Terminal)),
+    }
+
+    let main = maze.main_loop();
+    println!("Loop length: {} Enclosed: {}", main.len(), main.enclosed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Inside tiles can be reached by squeezing between pipes
+    const SQUEEZE: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+
+    const LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    #[test]
+    fn test_render_plain() {
+        let maze = Maze::parse(SQUEEZE).unwrap();
+        let expected = "OOOOOOOOOO
+OS══════╗O
+O║╔════╗║O
+O║║OOOO║║O
+O║║OOOO║║O
+O║╚═╗╔═╝║O
+O║II║║II║O
+O╚══╝╚══╝O
+OOOOOOOOOO
+";
+        assert_eq!(maze.render(RenderStyle::Plain), expected);
+    }
+
+    #[test]
+    fn test_render_terminal() {
+        colored::control::set_override(true);
+        let maze = Maze::parse(include_str!("../../example1.txt")).unwrap();
+        let output = maze.render(RenderStyle::Terminal);
+        assert!(output.contains("\u{1b}["));
+        let plain = maze.render(RenderStyle::Plain);
+        assert_eq!(plain, "OOOOO\nOS═╗O\nO║I║O\nO╚═╝O\nOOOOO\n");
+    }
+
+    #[test]
+    fn test_pick_matches_scanline() {
+        for (input, expected) in [
+            (include_str!("../../example1.txt"), 1),
+            (include_str!("../../example2.txt"), 1),
+            (include_str!("../../example3.txt"), 10),
+            (SQUEEZE, 4),
+            (LARGER, 8),
+            (include_str!("../../input.txt"), 443),
+        ] {
+            let maze = Maze::parse(input).unwrap();
+            let main = maze.main_loop();
+            assert_eq!(main.enclosed(), expected);
+            assert_eq!(maze.enclosed_scanline(&main), expected);
+            let plain = maze.render(RenderStyle::Plain);
+            assert_eq!(plain.chars().filter(|&c| c == 'I').count(), expected);
+        }
+    }
+
+    #[test]
+    fn test_shoelace_orientation() {
+        let maze = Maze::parse(include_str!("../../example1.txt")).unwrap();
+        let main = maze.main_loop();
+        let reversed = day10::maze::Loop {
+            tiles: main.tiles.iter().rev().copied().collect(),
+        };
+        assert_eq!(main.shoelace(), -reversed.shoelace());
+        assert_eq!(main.shoelace().abs(), 8);
+    }
+}
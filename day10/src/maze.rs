@@ -0,0 +1,411 @@
+use std::fmt;
+
+use glam::IVec2;
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    pub fn offset(self) -> IVec2 {
+        match self {
+            Direction::North => IVec2::new(0, -1),
+            Direction::South => IVec2::new(0, 1),
+            Direction::West => IVec2::new(-1, 0),
+            Direction::East => IVec2::new(1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::East => Direction::West,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pipe {
+    Vertical,
+    Horizontal,
+    BendNorthEast,
+    BendNorthWest,
+    BendSouthWest,
+    BendSouthEast,
+}
+
+impl Pipe {
+    pub const ALL: [Pipe; 6] = [
+        Pipe::Vertical,
+        Pipe::Horizontal,
+        Pipe::BendNorthEast,
+        Pipe::BendNorthWest,
+        Pipe::BendSouthWest,
+        Pipe::BendSouthEast,
+    ];
+
+    pub fn from_char(c: char) -> Option<Pipe> {
+        match c {
+            '|' => Some(Pipe::Vertical),
+            '-' => Some(Pipe::Horizontal),
+            'L' => Some(Pipe::BendNorthEast),
+            'J' => Some(Pipe::BendNorthWest),
+            '7' => Some(Pipe::BendSouthWest),
+            'F' => Some(Pipe::BendSouthEast),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Pipe::Vertical => '|',
+            Pipe::Horizontal => '-',
+            Pipe::BendNorthEast => 'L',
+            Pipe::BendNorthWest => 'J',
+            Pipe::BendSouthWest => '7',
+            Pipe::BendSouthEast => 'F',
+        }
+    }
+
+    pub fn connections(self) -> [Direction; 2] {
+        use Direction::*;
+
+        match self {
+            Pipe::Vertical => [North, South],
+            Pipe::Horizontal => [West, East],
+            Pipe::BendNorthEast => [North, East],
+            Pipe::BendNorthWest => [North, West],
+            Pipe::BendSouthWest => [South, West],
+            Pipe::BendSouthEast => [South, East],
+        }
+    }
+
+    pub fn connects(self, direction: Direction) -> bool {
+        self.connections().contains(&direction)
+    }
+
+    // The way out of the pipe when entering it from `from`
+    pub fn exit(self, from: Direction) -> Option<Direction> {
+        let [a, b] = self.connections();
+        if a == from {
+            Some(b)
+        } else if b == from {
+            Some(a)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MazeError {
+    Empty,
+    Ragged {
+        row: usize,
+    },
+    UnknownTile {
+        pos: IVec2,
+        tile: char,
+    },
+    NoStart,
+    MultipleStarts(Vec<IVec2>),
+    // More than one pipe type would close a loop through the start
+    AmbiguousStart {
+        pos: IVec2,
+        candidates: Vec<Pipe>,
+    },
+    // No pipe type closes a loop through the start
+    InconsistentStart {
+        pos: IVec2,
+        connected: Vec<Direction>,
+    },
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::Empty => write!(f, "maze is empty"),
+            MazeError::Ragged { row } => write!(f, "row {} has a different width", row),
+            MazeError::UnknownTile { pos, tile } => {
+                write!(f, "unknown tile {:?} at ({}, {})", tile, pos.x, pos.y)
+            }
+            MazeError::NoStart => write!(f, "maze has no start tile"),
+            MazeError::MultipleStarts(starts) => {
+                write!(f, "maze has {} start tiles", starts.len())
+            }
+            MazeError::AmbiguousStart { pos, candidates } => write!(
+                f,
+                "start at ({}, {}) is ambiguous, could be any of {}",
+                pos.x,
+                pos.y,
+                candidates.iter().map(|p| p.to_char()).collect::<String>()
+            ),
+            MazeError::InconsistentStart { pos, connected } => write!(
+                f,
+                "start at ({}, {}) is not on a closed loop, connected to {:?}",
+                pos.x, pos.y, connected
+            ),
+        }
+    }
+}
+
+impl From<MazeError> for String {
+    fn from(e: MazeError) -> String {
+        e.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop {
+    // Tiles in walking order, starting from the tile the loop was traced from
+    pub tiles: Vec<IVec2>,
+}
+
+impl Loop {
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    // Steps to the tile furthest away along the loop
+    pub fn farthest(&self) -> usize {
+        self.tiles.len() / 2
+    }
+
+    // Twice the signed area of the polygon through the tile centres
+    pub fn shoelace(&self) -> i64 {
+        self.tiles
+            .iter()
+            .zip(self.tiles.iter().cycle().skip(1))
+            .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+            .sum()
+    }
+
+    // Pick's theorem: A = i + b/2 - 1, with every loop tile a boundary point
+    pub fn enclosed(&self) -> usize {
+        let area = self.shoelace().unsigned_abs() as usize;
+        (area + 2 - self.tiles.len()) / 2
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Maze {
+    width: usize,
+    height: usize,
+    tiles: Vec<Option<Pipe>>,
+    start: IVec2,
+    start_pipe: Pipe,
+}
+
+impl Maze {
+    pub fn parse(input: &str) -> Result<Maze, MazeError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.first().map(|l| l.len()).ok_or(MazeError::Empty)?;
+        let height = lines.len();
+
+        let mut tiles = Vec::with_capacity(width * height);
+        let mut starts = vec![];
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(MazeError::Ragged { row: y });
+            }
+            for (x, c) in line.chars().enumerate() {
+                let pos = IVec2::new(x as i32, y as i32);
+                tiles.push(match c {
+                    '.' => None,
+                    'S' => {
+                        starts.push(pos);
+                        None
+                    }
+                    _ => Some(Pipe::from_char(c).ok_or(MazeError::UnknownTile { pos, tile: c })?),
+                });
+            }
+        }
+
+        let start = match starts[..] {
+            [] => return Err(MazeError::NoStart),
+            [start] => start,
+            _ => return Err(MazeError::MultipleStarts(starts)),
+        };
+
+        let mut maze = Maze {
+            width,
+            height,
+            tiles,
+            start,
+            start_pipe: Pipe::Vertical,
+        };
+        maze.start_pipe = maze.infer_start()?;
+        let index = maze.index(start).unwrap();
+        maze.tiles[index] = Some(maze.start_pipe);
+
+        Ok(maze)
+    }
+
+    fn infer_start(&self) -> Result<Pipe, MazeError> {
+        let connected = Direction::ALL
+            .into_iter()
+            .filter(|&d| {
+                self.get(self.start + d.offset())
+                    .is_some_and(|p| p.connects(d.opposite()))
+            })
+            .collect::<Vec<_>>();
+
+        let candidates = Pipe::ALL
+            .into_iter()
+            .filter(|p| p.connections().iter().all(|d| connected.contains(d)))
+            .filter(|&p| self.walk(self.start, p).1)
+            .collect::<Vec<_>>();
+
+        match candidates[..] {
+            [pipe] => Ok(pipe),
+            [] => Err(MazeError::InconsistentStart {
+                pos: self.start,
+                connected,
+            }),
+            _ => Err(MazeError::AmbiguousStart {
+                pos: self.start,
+                candidates,
+            }),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn start(&self) -> IVec2 {
+        self.start
+    }
+
+    pub fn start_pipe(&self) -> Pipe {
+        self.start_pipe
+    }
+
+    fn index(&self, pos: IVec2) -> Option<usize> {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.width || pos.y as usize >= self.height {
+            return None;
+        }
+        Some(pos.y as usize * self.width + pos.x as usize)
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<Pipe> {
+        self.index(pos).and_then(|i| self.tiles[i])
+    }
+
+    // Follows the pipe from `from` as if it were `pipe`, returning the tiles
+    // visited and whether the walk came back around to `from`
+    fn walk(&self, from: IVec2, pipe: Pipe) -> (Vec<IVec2>, bool) {
+        let mut tiles = vec![from];
+        let mut pos = from;
+        let mut heading = pipe.connections()[0];
+
+        loop {
+            pos += heading.offset();
+            let entry = heading.opposite();
+            if pos == from {
+                return (tiles, pipe.connects(entry));
+            }
+            heading = match self.get(pos).and_then(|p| p.exit(entry)) {
+                Some(h) => h,
+                None => return (tiles, false),
+            };
+            tiles.push(pos);
+            if tiles.len() > self.tiles.len() {
+                return (tiles, false);
+            }
+        }
+    }
+
+    pub fn trace(&self, from: IVec2) -> Option<Loop> {
+        let pipe = self.get(from)?;
+        match self.walk(from, pipe) {
+            (tiles, true) => Some(Loop { tiles }),
+            _ => None,
+        }
+    }
+
+    pub fn main_loop(&self) -> Loop {
+        self.trace(self.start)
+            .expect("start pipe is only inferred when it closes a loop")
+    }
+
+    // Every closed loop in the grid, in order of the first tile reached
+    // scanning row by row
+    pub fn loops(&self) -> Vec<Loop> {
+        let mut seen = vec![false; self.tiles.len()];
+        let mut loops = vec![];
+
+        for i in 0..self.tiles.len() {
+            let Some(pipe) = self.tiles[i] else {
+                continue;
+            };
+            if seen[i] {
+                continue;
+            }
+            let pos = IVec2::new((i % self.width) as i32, (i / self.width) as i32);
+            let (tiles, closed) = self.walk(pos, pipe);
+            for &t in &tiles {
+                seen[self.index(t).unwrap()] = true;
+            }
+            if closed {
+                loops.push(Loop { tiles });
+            }
+        }
+
+        loops
+    }
+
+    // Marks the tiles enclosed by `main` by scanning each row and flipping
+    // inside/outside whenever a loop tile with a northward connection is crossed
+    pub fn inside(&self, main: &Loop) -> Vec<bool> {
+        let mut on_loop = vec![false; self.tiles.len()];
+        for &pos in &main.tiles {
+            on_loop[self.index(pos).unwrap()] = true;
+        }
+
+        let mut inside = vec![false; self.tiles.len()];
+        inside
+            .par_chunks_mut(self.width)
+            .enumerate()
+            .for_each(|(y, row)| {
+                let mut crossed = false;
+                for (x, tile) in row.iter_mut().enumerate() {
+                    let i = y * self.width + x;
+                    if on_loop[i] {
+                        if self.tiles[i].is_some_and(|p| p.connects(Direction::North)) {
+                            crossed = !crossed;
+                        }
+                    } else {
+                        *tile = crossed;
+                    }
+                }
+            });
+        inside
+    }
+
+    pub fn enclosed_scanline(&self, main: &Loop) -> usize {
+        self.inside(main).into_iter().filter(|&i| i).count()
+    }
+}
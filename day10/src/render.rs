@@ -0,0 +1,77 @@
+use colored::Colorize;
+use glam::IVec2;
+
+use crate::maze::{Maze, Pipe};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    // Coloured for a terminal: loop green, enclosed tiles red, the rest dimmed
+    Terminal,
+    // Glyphs only, suitable for writing to a file
+    Plain,
+}
+
+pub fn glyph(pipe: Pipe) -> char {
+    match pipe {
+        Pipe::Vertical => '║',
+        Pipe::Horizontal => '═',
+        Pipe::BendNorthEast => '╚',
+        Pipe::BendNorthWest => '╝',
+        Pipe::BendSouthWest => '╗',
+        Pipe::BendSouthEast => '╔',
+    }
+}
+
+impl Maze {
+    // Draws the main loop with box-drawing glyphs; every other tile is shown
+    // as `I` when enclosed by the loop and `O` when outside it
+    pub fn render(&self, style: RenderStyle) -> String {
+        let main = self.main_loop();
+        let inside = self.inside(&main);
+        let mut on_loop = vec![false; self.width() * self.height()];
+        for pos in &main.tiles {
+            on_loop[pos.y as usize * self.width() + pos.x as usize] = true;
+        }
+
+        let mut output = String::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let i = y * self.width() + x;
+                let pos = IVec2::new(x as i32, y as i32);
+                let tile = if on_loop[i] {
+                    let c = if pos == self.start() {
+                        'S'
+                    } else {
+                        glyph(self.get(pos).unwrap())
+                    };
+                    (c, TileKind::Loop)
+                } else if inside[i] {
+                    ('I', TileKind::Inside)
+                } else {
+                    ('O', TileKind::Outside)
+                };
+                match style {
+                    RenderStyle::Plain => output.push(tile.0),
+                    RenderStyle::Terminal => {
+                        let s = tile.0.to_string();
+                        let s = match tile.1 {
+                            TileKind::Loop if pos == self.start() => s.yellow().bold(),
+                            TileKind::Loop => s.green(),
+                            TileKind::Inside => s.red().bold(),
+                            TileKind::Outside => s.dimmed(),
+                        };
+                        output.push_str(&s.to_string());
+                    }
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+enum TileKind {
+    Loop,
+    Inside,
+    Outside,
+}
@@ -0,0 +1,154 @@
+fn main() {
+    divan::main();
+}
+
+use day11::part1::run as run1;
+use day11::part2::run as run2;
+use divan::Bencher;
+use std::time::Duration;
+
+mod part1 {
+    use super::*;
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn main(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(run1)
+    }
+}
+
+mod part2 {
+    use super::*;
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn main(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(|input| run2(input, 1000000))
+    }
+}
+
+// Square image with roughly one galaxy in forty tiles and every seventh row
+// and column left empty so there is something to expand
+fn generate(galaxies: usize) -> String {
+    let side = ((galaxies * 40) as f64).sqrt() as usize;
+    let mut grid = vec![vec![b'.'; side]; side];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut placed = 0;
+    while placed < galaxies {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let x = (state % side as u64) as usize;
+        let y = ((state >> 32) % side as u64) as usize;
+        if x % 7 == 3 || y % 7 == 3 || grid[y][x] == b'#' {
+            continue;
+        }
+        grid[y][x] = b'#';
+        placed += 1;
+    }
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+mod generated {
+    use super::*;
+    #[divan::bench(args = [1_000, 10_000, 100_000], min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn prefix_sums(bencher: Bencher, galaxies: usize) {
+        let input = generate(galaxies);
+        bencher.bench(|| run2(&input, 1000000))
+    }
+
+    // 100k galaxies is five billion pairs and takes about three minutes, so
+    // every size gets a single pass
+    #[divan::bench(args = [1_000, 10_000, 100_000], sample_count = 1, sample_size = 1)]
+    fn pairwise(bencher: Bencher, galaxies: usize) {
+        let input = generate(galaxies);
+        bencher.bench(|| pairwise::run(&input, 1000000))
+    }
+}
+
+// The combinations-based implementation this crate used before switching to
+// per-axis prefix sums, kept as a reference point
+mod pairwise {
+    use glam::IVec2;
+    use itertools::Itertools;
+
+    struct Galaxy {
+        coord: IVec2,
+    }
+
+    impl Galaxy {
+        fn distance(&self, other: &Galaxy) -> usize {
+            let delta_x = (self.coord.x - other.coord.x).abs();
+            let delta_y = (self.coord.y - other.coord.y).abs();
+            delta_x as usize + delta_y as usize
+        }
+    }
+
+    fn fix_distortions(
+        galaxies: Vec<Galaxy>,
+        x_dist: &[i32],
+        y_dist: &[i32],
+        multiplier: i32,
+    ) -> Vec<Galaxy> {
+        galaxies
+            .into_iter()
+            .map(|mut galaxy| {
+                let xmove: i32 = x_dist
+                    .iter()
+                    .map(|dist| {
+                        if galaxy.coord.x > *dist {
+                            multiplier - 1
+                        } else {
+                            0
+                        }
+                    })
+                    .sum();
+                let ymove: i32 = y_dist
+                    .iter()
+                    .map(|dist| {
+                        if galaxy.coord.y > *dist {
+                            multiplier - 1
+                        } else {
+                            0
+                        }
+                    })
+                    .sum();
+                galaxy.coord.x += xmove;
+                galaxy.coord.y += ymove;
+                galaxy
+            })
+            .collect()
+    }
+
+    pub fn run(input: &str, multiplier: i32) -> usize {
+        let raw_map: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+        let mut galaxies = vec![];
+        for (y, row) in raw_map.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if c == b'#' {
+                    galaxies.push(Galaxy {
+                        coord: IVec2::new(x as i32, y as i32),
+                    });
+                }
+            }
+        }
+
+        let y_dist = (0..raw_map.len())
+            .filter(|&y| raw_map[y].iter().all(|&c| c != b'#'))
+            .map(|y| y as i32)
+            .collect::<Vec<_>>();
+        let x_dist = (0..raw_map[0].len())
+            .filter(|&x| raw_map.iter().all(|row| row[x] != b'#'))
+            .map(|x| x as i32)
+            .collect::<Vec<_>>();
+        let galaxies = fix_distortions(galaxies, &x_dist, &y_dist, multiplier);
+
+        galaxies
+            .iter()
+            .combinations(2)
+            .map(|combination| combination[0].distance(combination[1]))
+            .sum()
+    }
+}
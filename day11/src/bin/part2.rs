@@ -9,6 +9,7 @@ pub fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day11::expansion::{pairwise_sum, total_distance, Image};
 
     #[test]
     fn test_example_input() {
@@ -23,4 +24,42 @@ mod tests {
         let result = run(input, 100).unwrap();
         assert_eq!(result, 8410);
     }
+
+    #[test]
+    fn test_input() {
+        let input = include_str!("../../input.txt");
+        assert_eq!(run(input, 1000000).unwrap(), 649862989626);
+    }
+
+    #[test]
+    fn test_pairwise_sum() {
+        assert_eq!(pairwise_sum(vec![]), 0);
+        assert_eq!(pairwise_sum(vec![5]), 0);
+        assert_eq!(pairwise_sum(vec![7, 1, 4]), 3 + 6 + 3);
+        assert_eq!(pairwise_sum(vec![2, 2, 2]), 0);
+    }
+
+    #[test]
+    fn test_matches_every_pair() {
+        let image = Image::parse(include_str!("../../example.txt")).unwrap();
+        for factor in [1, 2, 10, 100, 1_000_000_000] {
+            let xs = image.expanded_xs(factor).unwrap();
+            let ys = image.expanded_ys(factor).unwrap();
+            let mut expected: u128 = 0;
+            for i in 0..xs.len() {
+                for j in i + 1..xs.len() {
+                    expected += xs[i].abs_diff(xs[j]) as u128 + ys[i].abs_diff(ys[j]) as u128;
+                }
+            }
+            assert_eq!(total_distance(&image, factor).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_invalid_factors() {
+        let input = include_str!("../../example.txt");
+        assert!(run(input, 0).is_err());
+        let image = Image::parse(input).unwrap();
+        assert!(total_distance(&image, u64::MAX).is_err());
+    }
 }
@@ -0,0 +1,120 @@
+use day11::universe::Universe;
+
+// Usage: universe <column factor> <row factor> [galaxy] [k]
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let mut args = std::env::args().skip(1).map(|a| a.parse::<u64>().unwrap());
+    let column_factor = args.next().unwrap_or(2);
+    let row_factor = args.next().unwrap_or(column_factor);
+    let galaxy = args.next().unwrap_or(1) as usize;
+    let k = args.next().unwrap_or(5) as usize;
+
+    let universe = Universe::parse(input, column_factor, row_factor).unwrap();
+    if let Ok(map) = universe.render() {
+        print!("{}", map);
+    }
+    let (width, height) = universe.size().unwrap();
+    println!(
+        "{} galaxies in {}x{}, total distance {}",
+        universe.len(),
+        width,
+        height,
+        universe.total_distance()
+    );
+    match universe.nearest(galaxy, k) {
+        Some(nearest) => {
+            for (other, distance) in nearest {
+                println!("Galaxy {} -> {}: {}", galaxy, other, distance);
+            }
+        }
+        None => println!("There is no galaxy {}", galaxy),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair_distances() {
+        let universe = Universe::parse(include_str!("../../example.txt"), 2, 2).unwrap();
+        assert_eq!(universe.len(), 9);
+        assert_eq!(universe.distance(5, 9), Some(9));
+        assert_eq!(universe.distance(1, 7), Some(15));
+        assert_eq!(universe.distance(3, 6), Some(17));
+        assert_eq!(universe.distance(8, 9), Some(5));
+        assert_eq!(universe.distance(9, 8), Some(5));
+        assert_eq!(universe.distance(0, 1), None);
+        assert_eq!(universe.distance(1, 10), None);
+        assert_eq!(universe.total_distance(), 374);
+    }
+
+    #[test]
+    fn test_render() {
+        let universe = Universe::parse(include_str!("../../example.txt"), 2, 2).unwrap();
+        let expected = "....#........
+.........#...
+#............
+.............
+.............
+........#....
+.#...........
+............#
+.............
+.............
+.........#...
+#....#.......
+";
+        assert_eq!(universe.render().unwrap(), expected);
+        assert_eq!(universe.size(), Some((13, 12)));
+
+        let huge = Universe::parse(include_str!("../../example.txt"), 1_000_000, 1).unwrap();
+        assert!(huge.render().is_err());
+    }
+
+    #[test]
+    fn test_separate_factors() {
+        let input = include_str!("../../example.txt");
+        let wide = Universe::parse(input, 10, 1).unwrap();
+        let tall = Universe::parse(input, 1, 10).unwrap();
+        let both = Universe::parse(input, 10, 10).unwrap();
+        let flat = Universe::parse(input, 1, 1).unwrap();
+        assert_eq!(both.total_distance(), 1030);
+        assert_eq!(
+            wide.total_distance() + tall.total_distance(),
+            both.total_distance() + flat.total_distance()
+        );
+        assert_eq!(wide.size(), Some((37, 10)));
+        assert_eq!(tall.size(), Some((10, 28)));
+        assert_eq!(tall.position(1), Some((3, 0)));
+        assert_eq!(tall.position(3), Some((0, 2)));
+        assert_eq!(tall.position(4), Some((6, 13)));
+    }
+
+    #[test]
+    fn test_huge_factors() {
+        let input = include_str!("../../example.txt");
+        let universe = Universe::parse(input, 1 << 40, 1 << 40).unwrap();
+        assert_eq!(
+            universe.position(9),
+            Some((4 + ((1 << 40) - 1), 9 + 2 * ((1 << 40) - 1)))
+        );
+        assert!(Universe::parse(input, u64::MAX, 2).is_err());
+        assert!(Universe::parse(input, 2, 0).is_err());
+    }
+
+    #[test]
+    fn test_nearest() {
+        let universe = Universe::parse(include_str!("../../example.txt"), 2, 2).unwrap();
+        let all = universe.nearest(5, 100).unwrap();
+        assert_eq!(all.len(), 8);
+        assert!(all.windows(2).all(|w| w[0].1 <= w[1].1));
+        for &(other, distance) in &all {
+            assert_eq!(universe.distance(5, other), Some(distance));
+        }
+        assert_eq!(universe.nearest(5, 3).unwrap(), all[..3].to_vec());
+        assert_eq!(universe.nearest(5, 0).unwrap(), vec![]);
+        assert_eq!(universe.nearest(8, 1).unwrap(), vec![(9, 5)]);
+        assert_eq!(universe.nearest(10, 1), None);
+    }
+}
@@ -0,0 +1,130 @@
+// Galaxy positions as they appear in the image, before any expansion
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    // (x, y) in reading order
+    pub galaxies: Vec<(usize, usize)>,
+}
+
+impl Image {
+    pub fn parse(input: &str) -> Result<Image, String> {
+        let mut galaxies = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            if *width.get_or_insert(line.len()) != line.len() {
+                return Err(format!("Row {} has a different width", y));
+            }
+            for (x, c) in line.bytes().enumerate() {
+                match c {
+                    b'#' => galaxies.push((x, y)),
+                    b'.' => {}
+                    _ => return Err(format!("Unknown tile {:?} at ({}, {})", c as char, x, y)),
+                }
+            }
+            height += 1;
+        }
+
+        Ok(Image {
+            width: width.unwrap_or(0),
+            height,
+            galaxies,
+        })
+    }
+
+    pub fn empty_columns(&self) -> usize {
+        empty_lines(self.width, self.galaxies.iter().map(|g| g.0)).1 as usize
+    }
+
+    pub fn empty_rows(&self) -> usize {
+        empty_lines(self.height, self.galaxies.iter().map(|g| g.1)).1 as usize
+    }
+
+    // For every column, how many empty columns come before it
+    pub fn empty_columns_before(&self) -> Vec<u64> {
+        empty_lines(self.width, self.galaxies.iter().map(|g| g.0)).0
+    }
+
+    // For every row, how many empty rows come before it
+    pub fn empty_rows_before(&self) -> Vec<u64> {
+        empty_lines(self.height, self.galaxies.iter().map(|g| g.1)).0
+    }
+
+    // Galaxy x coordinates after every empty column grows to `factor` columns
+    pub fn expanded_xs(&self, factor: u64) -> Result<Vec<u64>, String> {
+        expand(
+            &self.empty_columns_before(),
+            self.galaxies.iter().map(|g| g.0),
+            factor,
+        )
+    }
+
+    // Galaxy y coordinates after every empty row grows to `factor` rows
+    pub fn expanded_ys(&self, factor: u64) -> Result<Vec<u64>, String> {
+        expand(
+            &self.empty_rows_before(),
+            self.galaxies.iter().map(|g| g.1),
+            factor,
+        )
+    }
+}
+
+// For every line, how many empty lines come before it, and how many there are
+// in total
+fn empty_lines(len: usize, positions: impl Iterator<Item = usize>) -> (Vec<u64>, u64) {
+    let mut occupied = vec![false; len];
+    for p in positions {
+        occupied[p] = true;
+    }
+    let mut empty = 0;
+    let before = occupied
+        .into_iter()
+        .map(|o| {
+            let before = empty;
+            if !o {
+                empty += 1;
+            }
+            before
+        })
+        .collect();
+    (before, empty)
+}
+
+fn expand(
+    empty_before: &[u64],
+    positions: impl Iterator<Item = usize>,
+    factor: u64,
+) -> Result<Vec<u64>, String> {
+    if factor == 0 {
+        return Err("Expansion factor must be at least 1".to_string());
+    }
+    positions
+        .map(|p| {
+            empty_before[p]
+                .checked_mul(factor - 1)
+                .and_then(|extra| extra.checked_add(p as u64))
+                .ok_or_else(|| format!("Expanded coordinate of {} overflows", p))
+        })
+        .collect()
+}
+
+// Sum of |a - b| over every pair of coordinates. Once sorted, the i-th value
+// is the larger one in exactly i pairs and the smaller in the rest.
+pub fn pairwise_sum(mut coordinates: Vec<u64>) -> u128 {
+    coordinates.sort_unstable();
+    let mut prefix: u128 = 0;
+    let mut sum: u128 = 0;
+    for (i, &c) in coordinates.iter().enumerate() {
+        sum += c as u128 * i as u128 - prefix;
+        prefix += c as u128;
+    }
+    sum
+}
+
+// Total Manhattan distance between every pair of galaxies once each empty
+// row and column has grown to `factor` rows or columns
+pub fn total_distance(image: &Image, factor: u64) -> Result<u128, String> {
+    Ok(pairwise_sum(image.expanded_xs(factor)?) + pairwise_sum(image.expanded_ys(factor)?))
+}
@@ -0,0 +1,105 @@
+use crate::expansion::{pairwise_sum, Image};
+
+// Largest expanded map `render` will draw
+const RENDER_LIMIT: u64 = 1 << 24;
+
+// The image after expansion, with galaxies numbered from 1 in reading order
+#[derive(Debug, Clone)]
+pub struct Universe {
+    image: Image,
+    column_factor: u64,
+    row_factor: u64,
+    xs: Vec<u64>,
+    ys: Vec<u64>,
+}
+
+impl Universe {
+    pub fn new(image: Image, column_factor: u64, row_factor: u64) -> Result<Universe, String> {
+        let xs = image.expanded_xs(column_factor)?;
+        let ys = image.expanded_ys(row_factor)?;
+        Ok(Universe {
+            image,
+            column_factor,
+            row_factor,
+            xs,
+            ys,
+        })
+    }
+
+    pub fn parse(input: &str, column_factor: u64, row_factor: u64) -> Result<Universe, String> {
+        Universe::new(Image::parse(input)?, column_factor, row_factor)
+    }
+
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    pub fn position(&self, galaxy: usize) -> Option<(u64, u64)> {
+        let i = galaxy.checked_sub(1)?;
+        Some((*self.xs.get(i)?, *self.ys.get(i)?))
+    }
+
+    pub fn distance(&self, a: usize, b: usize) -> Option<u128> {
+        let (ax, ay) = self.position(a)?;
+        let (bx, by) = self.position(b)?;
+        Some(ax.abs_diff(bx) as u128 + ay.abs_diff(by) as u128)
+    }
+
+    // The `k` galaxies closest to `galaxy` as (number, distance), nearest
+    // first and by number when tied
+    pub fn nearest(&self, galaxy: usize, k: usize) -> Option<Vec<(usize, u128)>> {
+        self.position(galaxy)?;
+        let mut others = (1..=self.len())
+            .filter(|&other| other != galaxy)
+            .map(|other| (other, self.distance(galaxy, other).unwrap()))
+            .collect::<Vec<_>>();
+        let by_distance = |a: &(usize, u128), b: &(usize, u128)| a.1.cmp(&b.1).then(a.0.cmp(&b.0));
+        if k < others.len() {
+            others.select_nth_unstable_by(k, by_distance);
+            others.truncate(k);
+        }
+        others.sort_unstable_by(by_distance);
+        Some(others)
+    }
+
+    pub fn total_distance(&self) -> u128 {
+        pairwise_sum(self.xs.clone()) + pairwise_sum(self.ys.clone())
+    }
+
+    // Expanded size as (width, height)
+    pub fn size(&self) -> Option<(u64, u64)> {
+        let grow = |len: usize, empty: u64, factor: u64| {
+            empty
+                .checked_mul(factor - 1)
+                .and_then(|extra| extra.checked_add(len as u64))
+        };
+        let empty_columns = self.image.empty_columns() as u64;
+        let empty_rows = self.image.empty_rows() as u64;
+        Some((
+            grow(self.image.width, empty_columns, self.column_factor)?,
+            grow(self.image.height, empty_rows, self.row_factor)?,
+        ))
+    }
+
+    // Draws the expanded map, refusing when it would be too large to be useful
+    pub fn render(&self) -> Result<String, String> {
+        let (width, height) = self
+            .size()
+            .filter(|&(w, h)| w.checked_mul(h).is_some_and(|cells| cells <= RENDER_LIMIT))
+            .ok_or("Expanded universe is too large to render")?;
+        let (width, height) = (width as usize, height as usize);
+
+        let mut rows = vec![vec![b'.'; width]; height];
+        for (&x, &y) in self.xs.iter().zip(&self.ys) {
+            rows[y as usize][x as usize] = b'#';
+        }
+        Ok(rows
+            .into_iter()
+            .map(|row| String::from_utf8(row).unwrap() + "\n")
+            .collect())
+    }
+}
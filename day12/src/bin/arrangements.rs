@@ -0,0 +1,118 @@
+use day12::springs::Row;
+use num_bigint::BigUint;
+
+// Usage: arrangements [limit] [samples]
+// Lists up to `limit` arrangements for every row, then draws `samples`
+// random arrangements for each
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let mut args = std::env::args()
+        .skip(1)
+        .map(|a| a.parse::<usize>().unwrap());
+    let limit = args.next().unwrap_or(3);
+    let samples = args.next().unwrap_or(0);
+    let mut rng = rand::thread_rng();
+
+    for line in input.lines() {
+        let row = Row::parse(line).unwrap();
+        let sampler = row.sampler::<BigUint>().unwrap();
+        println!("{} ({} arrangements)", line, sampler.count());
+        for arrangement in row.arrangements(Some(limit)) {
+            println!("  {}", arrangement);
+        }
+        for _ in 0..samples {
+            println!("  ~ {}", sampler.sample(&mut rng).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    // Every `.#` string of the right length that matches the row
+    fn brute_force(row: &Row) -> Vec<String> {
+        let n = row.conditions.len();
+        let mut all = (0..1u32 << n)
+            .map(|bits| {
+                (0..n)
+                    .map(|i| if bits >> i & 1 == 1 { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .filter(|s| row.matches(s))
+            .collect::<Vec<_>>();
+        all.sort();
+        all
+    }
+
+    #[test]
+    fn test_arrangements_of_first_row() {
+        let row = Row::parse("???.### 1,1,3").unwrap();
+        let all = row.arrangements(None).collect::<Vec<_>>();
+        assert_eq!(all, vec!["#.#.###"]);
+    }
+
+    #[test]
+    fn test_arrangements_match_brute_force() {
+        for line in include_str!("../../example.txt").lines() {
+            let row = Row::parse(line).unwrap();
+            let mut all = row.arrangements(None).collect::<Vec<_>>();
+            assert_eq!(all.len(), row.count::<usize>().unwrap());
+            assert!(all.iter().all(|a| row.matches(a)));
+            all.sort();
+            assert_eq!(all, brute_force(&row));
+        }
+    }
+
+    #[test]
+    fn test_arrangements_order() {
+        let row = Row::parse("?###???????? 3,2,1").unwrap();
+        let all = row.arrangements(None).collect::<Vec<_>>();
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0], ".###.##.#...");
+        assert_eq!(all[9], ".###....##.#");
+    }
+
+    #[test]
+    fn test_limit() {
+        let row = Row::parse("?###???????? 3,2,1").unwrap();
+        assert_eq!(row.arrangements(Some(4)).count(), 4);
+        assert_eq!(row.arrangements(Some(0)).count(), 0);
+        assert_eq!(row.arrangements(Some(100)).count(), 10);
+
+        // Far too many to list, but the first few come straight away
+        let unfolded = Row::parse(&format!("{} 1,1", "?".repeat(200))).unwrap();
+        assert_eq!(unfolded.count::<usize>().unwrap(), 199 * 198 / 2);
+        let first = unfolded.arrangements(Some(2)).collect::<Vec<_>>();
+        assert!(first[0].starts_with("#.#."));
+        assert!(first[1].starts_with("#..#."));
+    }
+
+    #[test]
+    fn test_no_arrangements() {
+        let row = Row::parse("#.# 3").unwrap();
+        assert_eq!(row.count::<usize>().unwrap(), 0);
+        assert_eq!(row.arrangements(None).next(), None);
+        assert_eq!(row.sample(&mut StdRng::seed_from_u64(1)), None);
+    }
+
+    #[test]
+    fn test_sampling_is_uniform() {
+        let row = Row::parse("?###???????? 3,2,1").unwrap();
+        let sampler = row.sampler::<u64>().unwrap();
+        let mut rng = StdRng::seed_from_u64(12);
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let draws = 20_000;
+        for _ in 0..draws {
+            let s = sampler.sample(&mut rng).unwrap();
+            assert!(row.matches(&s));
+            *seen.entry(s).or_default() += 1;
+        }
+        assert_eq!(seen.len(), 10);
+        // Each arrangement should turn up about 2000 times
+        assert!(seen.values().all(|&n| (1700..2300).contains(&n)));
+    }
+}
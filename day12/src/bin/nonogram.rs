@@ -0,0 +1,178 @@
+use day12::nonogram::{Nonogram, Solution};
+
+// Usage: nonogram [puzzle file]
+pub fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => include_str!("../../nonogram.txt").to_string(),
+    };
+    let puzzle = Nonogram::parse(&input).unwrap();
+
+    match puzzle.solve() {
+        Solution::Unique(grid) => {
+            println!("Unique solution:");
+            print!("{}", puzzle.render(&grid));
+        }
+        Solution::Multiple(a, b) => {
+            println!("Multiple solutions, for example:");
+            print!("{}", puzzle.render(&a));
+            println!("and:");
+            print!("{}", puzzle.render(&b));
+        }
+        Solution::Contradiction => println!("No solution"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day12::nonogram::Grid;
+    use day12::springs::{Row, Tile};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn satisfies(puzzle: &Nonogram, grid: &Grid) -> bool {
+        grid.is_complete()
+            && Nonogram::from_grid(grid).rows == puzzle.rows
+            && Nonogram::from_grid(grid).columns == puzzle.columns
+    }
+
+    #[test]
+    fn test_heart() {
+        let puzzle = Nonogram::parse(include_str!("../../nonogram.txt")).unwrap();
+        let expected = Grid::parse(
+            "..##..##..
+.####.###.
+##########
+##########
+.########.
+..######..
+...####...
+....##....
+",
+        )
+        .unwrap();
+        assert_eq!(puzzle.solve(), Solution::Unique(expected));
+    }
+
+    #[test]
+    fn test_propagation_alone() {
+        // Every line here is forced without guessing
+        let puzzle = Nonogram::parse("??? 3\n??? 1\n??? 1,1\ncolumns: 1,1 2 1,1\n").unwrap();
+        let mut grid = puzzle.start.clone();
+        assert!(puzzle.propagate(&mut grid));
+        assert_eq!(grid.to_string(), "###\n.#.\n#.#\n");
+    }
+
+    #[test]
+    fn test_multiple() {
+        let puzzle = Nonogram::parse("?? 1\n?? 1\ncolumns: 1 1\n").unwrap();
+        match puzzle.solve() {
+            Solution::Multiple(a, b) => {
+                assert_ne!(a, b);
+                assert!(satisfies(&puzzle, &a));
+                assert!(satisfies(&puzzle, &b));
+            }
+            other => panic!("expected two solutions, got {:?}", other),
+        }
+        // Fixing one cell makes it unique
+        let puzzle = Nonogram::parse("#? 1\n?? 1\ncolumns: 1 1\n").unwrap();
+        assert_eq!(
+            puzzle.solve(),
+            Solution::Unique(Grid::parse("#.\n.#\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let puzzle = Nonogram::parse("?? 2\ncolumns: 1 0\n").unwrap();
+        assert_eq!(puzzle.solve(), Solution::Contradiction);
+        // Each line is fine alone but the totals disagree
+        let puzzle = Nonogram::parse("??? 1\n??? 1\ncolumns: 1 1 1\n").unwrap();
+        assert_eq!(puzzle.solve(), Solution::Contradiction);
+        let puzzle = Nonogram::parse(".? 1\n?? 0\ncolumns: 1 0\n").unwrap();
+        assert_eq!(puzzle.solve(), Solution::Contradiction);
+    }
+
+    #[test]
+    fn test_parse_render_roundtrip() {
+        let input = "#?. 1\n??? 0\n?#? 1,1\ncolumns: 1,1 1 1\n";
+        let puzzle = Nonogram::parse(input).unwrap();
+        assert_eq!(puzzle.rows, vec![vec![1], vec![], vec![1, 1]]);
+        assert_eq!(puzzle.render(&puzzle.start), input);
+        assert_eq!(
+            Nonogram::parse(&puzzle.render(&puzzle.start)).unwrap(),
+            puzzle
+        );
+        assert!(Nonogram::parse("?? 1\n").is_err());
+        assert!(Nonogram::parse("?? 1\ncolumns: 1\n").is_err());
+        assert!(Nonogram::parse("?x 1\ncolumns: 1 0\n").is_err());
+    }
+
+    #[test]
+    fn test_refine() {
+        let row = Row::parse("??????? 5").unwrap();
+        let refined = row.refine().unwrap();
+        let line = refined.into_iter().map(Tile::to_char).collect::<String>();
+        assert_eq!(line, "??###??");
+        let row = Row::parse("?#??.?? 3,1").unwrap();
+        let line = row
+            .refine()
+            .unwrap()
+            .into_iter()
+            .map(Tile::to_char)
+            .collect::<String>();
+        assert_eq!(line, "?##?.??");
+        assert_eq!(Row::parse("#.# 3").unwrap().refine(), None);
+    }
+
+    #[test]
+    fn test_refine_agrees_with_arrangements() {
+        for line in include_str!("../../example.txt").lines() {
+            let row = Row::parse(line).unwrap();
+            let all = row.arrangements(None).collect::<Vec<_>>();
+            let expected = (0..row.conditions.len())
+                .map(|i| {
+                    let first = all[0].as_bytes()[i];
+                    if all.iter().all(|a| a.as_bytes()[i] == first) {
+                        first as char
+                    } else {
+                        '?'
+                    }
+                })
+                .collect::<String>();
+            let refined = row.refine().unwrap().into_iter().map(Tile::to_char);
+            assert_eq!(refined.collect::<String>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_random_pictures() {
+        let mut rng = StdRng::seed_from_u64(45);
+        for _ in 0..200 {
+            let width = rng.gen_range(1..9);
+            let height = rng.gen_range(1..9);
+            let mut picture = Grid::unknown(width, height);
+            for y in 0..height {
+                for x in 0..width {
+                    let tile = if rng.gen_bool(0.55) {
+                        Tile::Damaged
+                    } else {
+                        Tile::Operational
+                    };
+                    picture.set(x, y, tile);
+                }
+            }
+            let puzzle = Nonogram::from_grid(&picture);
+            match puzzle.solve() {
+                Solution::Unique(grid) => assert_eq!(grid, picture),
+                Solution::Multiple(a, b) => {
+                    assert_ne!(a, b);
+                    assert!(satisfies(&puzzle, &a));
+                    assert!(satisfies(&puzzle, &b));
+                }
+                Solution::Contradiction => panic!("{} has a solution", picture),
+            }
+        }
+    }
+}
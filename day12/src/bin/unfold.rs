@@ -0,0 +1,81 @@
+use day12::part2::run_unfolded;
+use num_bigint::BigUint;
+
+// Usage: unfold [factor]
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let factor = std::env::args()
+        .nth(1)
+        .map_or(5, |a| a.parse::<usize>().unwrap());
+
+    println!("Unfolded {} times", factor);
+    match run_unfolded::<u64>(input, factor) {
+        Ok(total) => println!("u64:    {}", total),
+        Err(e) => println!("u64:    {}", e),
+    }
+    match run_unfolded::<u128>(input, factor) {
+        Ok(total) => println!("u128:   {}", total),
+        Err(e) => println!("u128:   {}", e),
+    }
+    println!(
+        "BigUint: {}",
+        run_unfolded::<BigUint>(input, factor).unwrap()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day12::springs::Row;
+
+    #[test]
+    fn test_factor_five_matches_part2() {
+        let input = include_str!("../../example.txt");
+        assert_eq!(run_unfolded::<u64>(input, 5).unwrap(), 525152);
+        assert_eq!(run_unfolded::<u128>(input, 5).unwrap(), 525152);
+        assert_eq!(
+            run_unfolded::<BigUint>(input, 5).unwrap(),
+            BigUint::from(525152u32)
+        );
+    }
+
+    #[test]
+    fn test_factor_one_matches_part1() {
+        let input = include_str!("../../example.txt");
+        assert_eq!(run_unfolded::<usize>(input, 1).unwrap(), 21);
+    }
+
+    #[test]
+    fn test_unfold() {
+        let row = Row::parse(".# 1").unwrap();
+        assert_eq!(
+            row.unfold(5),
+            Row::parse(".#?.#?.#?.#?.# 1,1,1,1,1").unwrap()
+        );
+        assert_eq!(row.unfold(1), row);
+    }
+
+    #[test]
+    fn test_overflow_is_detected() {
+        let input = include_str!("../../example.txt");
+        // The last example row alone has 506250 arrangements at factor 5,
+        // so u32 gives out long before factor 20
+        assert!(run_unfolded::<u32>(input, 20).is_err());
+        let exact = run_unfolded::<BigUint>(input, 20).unwrap();
+        match run_unfolded::<u128>(input, 20) {
+            Ok(total) => assert_eq!(BigUint::from(total), exact),
+            Err(_) => assert!(exact > BigUint::from(u128::MAX)),
+        }
+        let huge = run_unfolded::<BigUint>(input, 50).unwrap();
+        assert!(huge > BigUint::from(u128::MAX));
+        assert!(run_unfolded::<u128>(input, 50).is_err());
+    }
+
+    #[test]
+    fn test_wide_groups() {
+        let row = Row::parse(&format!("{} 300", "?".repeat(305))).unwrap();
+        assert_eq!(row.count::<u64>().unwrap(), 6);
+        assert!(Row::parse("??? 0").is_err());
+        assert!(Row::parse("??? x").is_err());
+    }
+}
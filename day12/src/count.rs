@@ -0,0 +1,81 @@
+use num_bigint::{BigUint, RandBigInt};
+use rand::Rng;
+
+// A number type the arrangement DP can count in. Addition is checked so a
+// count that does not fit is reported rather than wrapped.
+pub trait Count: Clone + PartialOrd {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+// Counts that can also drive uniform sampling
+pub trait Sample: Count {
+    // A uniformly random value in 0..self
+    fn random_below<R: Rng + ?Sized>(&self, rng: &mut R) -> Self;
+}
+
+macro_rules! primitive_count {
+    ($($t:ty),*) => {$(
+        impl Count for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn one() -> Self {
+                1
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+        }
+
+        impl Sample for $t {
+            fn random_below<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+                rng.gen_range(0..*self)
+            }
+        }
+    )*};
+}
+
+primitive_count!(u32, u64, u128, usize);
+
+impl Count for BigUint {
+    fn zero() -> Self {
+        BigUint::ZERO
+    }
+
+    fn one() -> Self {
+        BigUint::from(1u8)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+impl Sample for BigUint {
+    fn random_below<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        rng.gen_biguint_below(self)
+    }
+}
+
+// Only tracks whether any arrangement exists, which is all enumeration needs
+impl Count for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn one() -> Self {
+        true
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(*self || *other)
+    }
+}
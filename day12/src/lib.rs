@@ -1,2 +1,5 @@
+pub mod count;
+pub mod nonogram;
 pub mod part1;
 pub mod part2;
+pub mod springs;
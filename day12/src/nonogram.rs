@@ -0,0 +1,288 @@
+use std::fmt;
+
+use crate::springs::{Row, Tile};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Tile>,
+}
+
+impl Grid {
+    pub fn unknown(width: usize, height: usize) -> Grid {
+        Grid {
+            width,
+            height,
+            cells: vec![Tile::Unknown; width * height],
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Grid, String> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |l| l.len());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(format!("Row {} has a different width", y));
+            }
+            for c in line.chars() {
+                cells.push(Tile::from_char(c).ok_or_else(|| format!("Unknown tile {:?}", c))?);
+            }
+        }
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Tile {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, tile: Tile) {
+        self.cells[y * self.width + x] = tile;
+    }
+
+    pub fn row(&self, y: usize) -> Vec<Tile> {
+        self.cells[y * self.width..(y + 1) * self.width].to_vec()
+    }
+
+    pub fn column(&self, x: usize) -> Vec<Tile> {
+        (0..self.height).map(|y| self.get(x, y)).collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.cells.contains(&Tile::Unknown)
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            let line = self
+                .row(y)
+                .into_iter()
+                .map(Tile::to_char)
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+// Damaged runs along a line, the way the rows of the spring puzzle are clued
+fn clue_of(line: &[Tile]) -> Vec<usize> {
+    line.split(|&t| t != Tile::Damaged)
+        .map(|run| run.len())
+        .filter(|&len| len > 0)
+        .collect()
+}
+
+fn parse_clue(clue: &str) -> Result<Vec<usize>, String> {
+    if clue == "0" {
+        return Ok(vec![]);
+    }
+    clue.split(',')
+        .map(|g| match g.parse::<usize>() {
+            Ok(0) | Err(_) => Err(format!("Bad clue {:?}", clue)),
+            Ok(g) => Ok(g),
+        })
+        .collect()
+}
+
+fn format_clue(clue: &[usize]) -> String {
+    if clue.is_empty() {
+        return "0".to_string();
+    }
+    clue.iter()
+        .map(|g| g.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Grid),
+    // Two different grids that both satisfy the clues
+    Multiple(Grid, Grid),
+    Contradiction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    pub rows: Vec<Vec<usize>>,
+    pub columns: Vec<Vec<usize>>,
+    // Cells already known, `?` for the rest
+    pub start: Grid,
+}
+
+impl Nonogram {
+    pub fn new(rows: Vec<Vec<usize>>, columns: Vec<Vec<usize>>) -> Nonogram {
+        let start = Grid::unknown(columns.len(), rows.len());
+        Nonogram {
+            rows,
+            columns,
+            start,
+        }
+    }
+
+    // The puzzle whose solution is `grid`
+    pub fn from_grid(grid: &Grid) -> Nonogram {
+        Nonogram::new(
+            (0..grid.height).map(|y| clue_of(&grid.row(y))).collect(),
+            (0..grid.width).map(|x| clue_of(&grid.column(x))).collect(),
+        )
+    }
+
+    // Each grid row is followed by its clue, as in the spring records, and a
+    // final line lists the column clues:
+    //
+    //   ??? 1,1
+    //   #?? 3
+    //   columns: 2 1 2
+    //
+    // An empty clue is written as 0.
+    pub fn parse(input: &str) -> Result<Nonogram, String> {
+        let mut grid = String::new();
+        let mut rows = vec![];
+        let mut columns = None;
+
+        for line in input.lines() {
+            if let Some(clues) = line.strip_prefix("columns:") {
+                columns = Some(
+                    clues
+                        .split_whitespace()
+                        .map(parse_clue)
+                        .collect::<Result<Vec<_>, _>>()?,
+                );
+                continue;
+            }
+            let (cells, clue) = line
+                .split_once(' ')
+                .ok_or_else(|| format!("Missing clue in {:?}", line))?;
+            grid.push_str(cells);
+            grid.push('\n');
+            rows.push(parse_clue(clue)?);
+        }
+
+        let columns = columns.ok_or("Missing column clues")?;
+        let start = Grid::parse(&grid)?;
+        if start.width != columns.len() {
+            return Err(format!(
+                "{} column clues for {} columns",
+                columns.len(),
+                start.width
+            ));
+        }
+        Ok(Nonogram {
+            rows,
+            columns,
+            start,
+        })
+    }
+
+    // The puzzle in the format `parse` reads, with `grid` in place of the
+    // starting cells
+    pub fn render(&self, grid: &Grid) -> String {
+        let mut output = String::new();
+        for (y, clue) in self.rows.iter().enumerate() {
+            let line = grid
+                .row(y)
+                .into_iter()
+                .map(Tile::to_char)
+                .collect::<String>();
+            output += &format!("{} {}\n", line, format_clue(clue));
+        }
+        let columns = self
+            .columns
+            .iter()
+            .map(|c| format_clue(c))
+            .collect::<Vec<_>>()
+            .join(" ");
+        output += &format!("columns: {}\n", columns);
+        output
+    }
+
+    // Runs the spring row solver over every row and column until nothing
+    // changes. Returns false if some line has no valid arrangement.
+    pub fn propagate(&self, grid: &mut Grid) -> bool {
+        let mut dirty_rows = vec![true; grid.height];
+        let mut dirty_columns = vec![true; grid.width];
+
+        while dirty_rows.contains(&true) || dirty_columns.contains(&true) {
+            for (y, groups) in self.rows.iter().enumerate() {
+                if !std::mem::take(&mut dirty_rows[y]) {
+                    continue;
+                }
+                let row = Row {
+                    conditions: grid.row(y),
+                    groups: groups.clone(),
+                };
+                let Some(refined) = row.refine() else {
+                    return false;
+                };
+                for (x, tile) in refined.into_iter().enumerate() {
+                    if tile != grid.get(x, y) {
+                        grid.set(x, y, tile);
+                        dirty_columns[x] = true;
+                    }
+                }
+            }
+            for (x, groups) in self.columns.iter().enumerate() {
+                if !std::mem::take(&mut dirty_columns[x]) {
+                    continue;
+                }
+                let column = Row {
+                    conditions: grid.column(x),
+                    groups: groups.clone(),
+                };
+                let Some(refined) = column.refine() else {
+                    return false;
+                };
+                for (y, tile) in refined.into_iter().enumerate() {
+                    if tile != grid.get(x, y) {
+                        grid.set(x, y, tile);
+                        dirty_rows[y] = true;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    pub fn solve(&self) -> Solution {
+        let mut found = vec![];
+        self.search(self.start.clone(), &mut found);
+        match found.len() {
+            0 => Solution::Contradiction,
+            1 => Solution::Unique(found.pop().unwrap()),
+            _ => {
+                let second = found.pop().unwrap();
+                Solution::Multiple(found.pop().unwrap(), second)
+            }
+        }
+    }
+
+    // Propagates, then guesses the first unknown cell both ways. Stops once
+    // two solutions are known since that already rules out uniqueness.
+    fn search(&self, mut grid: Grid, found: &mut Vec<Grid>) {
+        if !self.propagate(&mut grid) {
+            return;
+        }
+        let Some(i) = grid.cells.iter().position(|&t| t == Tile::Unknown) else {
+            found.push(grid);
+            return;
+        };
+        for guess in [Tile::Damaged, Tile::Operational] {
+            let mut next = grid.clone();
+            next.cells[i] = guess;
+            self.search(next, found);
+            if found.len() > 1 {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use num_bigint::BigUint;
+use rand::Rng;
+
+use crate::count::{Count, Sample};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Operational),
+            '#' => Some(Tile::Damaged),
+            '?' => Some(Tile::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Operational => '.',
+            Tile::Damaged => '#',
+            Tile::Unknown => '?',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub conditions: Vec<Tile>,
+    pub groups: Vec<usize>,
+}
+
+impl Row {
+    pub fn parse(line: &str) -> Result<Row, String> {
+        let (conditions, groups) = line
+            .split_once(' ')
+            .ok_or_else(|| format!("Missing groups in {:?}", line))?;
+        let conditions = conditions
+            .chars()
+            .map(|c| Tile::from_char(c).ok_or_else(|| format!("Unknown tile {:?}", c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let groups = groups
+            .split(',')
+            .map(|g| {
+                g.parse::<usize>()
+                    .map_err(|e| format!("Bad group {:?}: {}", g, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if groups.contains(&0) {
+            return Err(format!("Empty group in {:?}", line));
+        }
+        Ok(Row { conditions, groups })
+    }
+
+    // Whether a concrete `.#` string is consistent with the row
+    pub fn matches(&self, arrangement: &str) -> bool {
+        if arrangement.len() != self.conditions.len() {
+            return false;
+        }
+        let fits = arrangement
+            .chars()
+            .zip(&self.conditions)
+            .all(|(c, &tile)| tile == Tile::Unknown || Tile::from_char(c) == Some(tile));
+        let groups = arrangement
+            .split('.')
+            .filter(|run| !run.is_empty())
+            .map(|run| run.len())
+            .collect::<Vec<_>>();
+        fits && groups.iter().copied().eq(self.groups.iter().copied())
+    }
+
+    // The row repeated `factor` times, joined by unknown springs
+    pub fn unfold(&self, factor: usize) -> Row {
+        let mut conditions = Vec::with_capacity((self.conditions.len() + 1) * factor);
+        for copy in 0..factor {
+            if copy > 0 {
+                conditions.push(Tile::Unknown);
+            }
+            conditions.extend_from_slice(&self.conditions);
+        }
+        Row {
+            conditions,
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    pub fn count<C: Count>(&self) -> Result<C, String> {
+        Ok(Table::<C>::new(self)?.ways[0][0].clone())
+    }
+
+    // Every valid arrangement, stopping after `limit` if given
+    pub fn arrangements(&self, limit: Option<usize>) -> Arrangements {
+        let table = Table::<bool>::new(self).unwrap();
+        let mut stack = vec![];
+        table.push_choices(&mut stack, 0, 0);
+        Arrangements {
+            table,
+            stack,
+            buffer: vec![],
+            remaining: limit,
+        }
+    }
+
+    pub fn sampler<C: Sample>(&self) -> Result<Sampler<C>, String> {
+        Ok(Sampler {
+            table: Table::new(self)?,
+        })
+    }
+
+    // A uniformly random valid arrangement, if there is one
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<String> {
+        self.sampler::<BigUint>().unwrap().sample(rng)
+    }
+
+    // Fixes every unknown spring that is the same in all valid arrangements,
+    // or returns None if there are none
+    pub fn refine(&self) -> Option<Vec<Tile>> {
+        let table = Table::<bool>::new(self).unwrap();
+        if !table.ways[0][0] {
+            return None;
+        }
+        let n = table.conditions.len();
+        let groups = table.groups.len();
+
+        // reach[g][i]: groups[..g] can be placed in conditions[..i]. Walking
+        // forward only along branches with a valid suffix means every choice
+        // taken here is part of some complete arrangement.
+        let mut reach = vec![vec![false; n + 1]; groups + 1];
+        reach[0][0] = true;
+        let mut operational = vec![false; n];
+        let mut damaged = vec![0isize; n + 1];
+        for i in 0..n {
+            for g in 0..=groups {
+                if !reach[g][i] {
+                    continue;
+                }
+                let (skip, place) = table.choices(g, i);
+                if skip {
+                    operational[i] = true;
+                    reach[g][i + 1] = true;
+                }
+                if place {
+                    let len = table.groups[g];
+                    damaged[i] += 1;
+                    damaged[i + len] -= 1;
+                    // The gap after the group
+                    operational[i + len] = true;
+                    reach[g + 1][i + len + 1] = true;
+                }
+            }
+        }
+
+        let mut covered = 0;
+        Some(
+            (0..self.conditions.len())
+                .map(|i| {
+                    covered += damaged[i];
+                    match (operational[i], covered > 0) {
+                        (true, false) => Tile::Operational,
+                        (false, true) => Tile::Damaged,
+                        _ => Tile::Unknown,
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+// ways[g][i] is the number of arrangements of conditions[i..] using
+// groups[g..]. The conditions get a trailing operational spring so a group
+// can always be followed by a gap.
+#[derive(Debug, Clone)]
+struct Table<C> {
+    conditions: Vec<Tile>,
+    groups: Vec<usize>,
+    ways: Vec<Vec<C>>,
+}
+
+impl<C: Count> Table<C> {
+    fn new(row: &Row) -> Result<Table<C>, String> {
+        let mut conditions = row.conditions.clone();
+        conditions.push(Tile::Operational);
+        let groups = row.groups.clone();
+        let n = conditions.len();
+
+        let mut ways = vec![vec![C::zero(); n + 1]; groups.len() + 1];
+        ways[groups.len()][n] = C::one();
+        for i in (0..n).rev() {
+            if conditions[i] != Tile::Damaged {
+                ways[groups.len()][i] = ways[groups.len()][i + 1].clone();
+            }
+        }
+        let mut table = Table {
+            conditions,
+            groups,
+            ways,
+        };
+        for g in (0..table.groups.len()).rev() {
+            for i in (0..n).rev() {
+                let (skip, place) = table.choices(g, i);
+                table.ways[g][i] = skip.checked_add(&place).ok_or_else(|| {
+                    format!("Arrangement count overflows {}", std::any::type_name::<C>())
+                })?;
+            }
+        }
+
+        Ok(table)
+    }
+
+    // Whether a group of `len` damaged springs can start at `i`
+    fn fits(&self, i: usize, len: usize) -> bool {
+        let conditions = &self.conditions;
+        len < conditions.len() - i
+            && !conditions[i..i + len].contains(&Tile::Operational)
+            && conditions[i + len] != Tile::Damaged
+    }
+
+    // The ways forward from (g, i) as (skip, place group) counts
+    fn choices(&self, g: usize, i: usize) -> (C, C) {
+        let skip = if self.conditions[i] != Tile::Damaged {
+            self.ways[g][i + 1].clone()
+        } else {
+            C::zero()
+        };
+        let place = if g < self.groups.len() && self.fits(i, self.groups[g]) {
+            self.ways[g + 1][i + self.groups[g] + 1].clone()
+        } else {
+            C::zero()
+        };
+        (skip, place)
+    }
+
+    fn push_choices(&self, stack: &mut Vec<Step>, g: usize, i: usize) {
+        if i == self.conditions.len() {
+            return;
+        }
+        let (skip, place) = self.choices(g, i);
+        // Pushed in reverse so groups are placed as early as possible first
+        if !skip.is_zero() {
+            stack.push(Step::Skip { g, i });
+        }
+        if !place.is_zero() {
+            stack.push(Step::Place { g, i });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Skip { g: usize, i: usize },
+    Place { g: usize, i: usize },
+}
+
+// Depth-first walk over the DP table that only follows branches with at least
+// one arrangement below them, so every step makes progress towards a result
+pub struct Arrangements {
+    table: Table<bool>,
+    stack: Vec<Step>,
+    buffer: Vec<u8>,
+    remaining: Option<usize>,
+}
+
+impl Iterator for Arrangements {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        let n = self.table.conditions.len();
+
+        while let Some(step) = self.stack.pop() {
+            let (g, i) = match step {
+                Step::Skip { g, i } => {
+                    self.buffer.truncate(i);
+                    self.buffer.push(b'.');
+                    (g, i + 1)
+                }
+                Step::Place { g, i } => {
+                    self.buffer.truncate(i);
+                    let len = self.table.groups[g];
+                    self.buffer.extend(std::iter::repeat_n(b'#', len));
+                    self.buffer.push(b'.');
+                    (g + 1, i + len + 1)
+                }
+            };
+            if i == n {
+                if let Some(remaining) = self.remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                return Some(String::from_utf8(self.buffer[..n - 1].to_vec()).unwrap());
+            }
+            self.table.push_choices(&mut self.stack, g, i);
+        }
+
+        None
+    }
+}
+
+pub struct Sampler<C> {
+    table: Table<C>,
+}
+
+impl<C: Sample> Sampler<C> {
+    pub fn count(&self) -> &C {
+        &self.table.ways[0][0]
+    }
+
+    // Walks the table choosing each branch in proportion to how many
+    // arrangements lie below it, which makes every arrangement equally likely
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<String> {
+        if self.count().is_zero() {
+            return None;
+        }
+        let n = self.table.conditions.len();
+        let mut arrangement = Vec::with_capacity(n);
+        let (mut g, mut i) = (0, 0);
+
+        while i < n {
+            let (skip, _) = self.table.choices(g, i);
+            if self.table.ways[g][i].random_below(rng) < skip {
+                arrangement.push(b'.');
+                i += 1;
+            } else {
+                let len = self.table.groups[g];
+                arrangement.extend(std::iter::repeat_n(b'#', len));
+                arrangement.push(b'.');
+                g += 1;
+                i += len + 1;
+            }
+        }
+
+        arrangement.truncate(n - 1);
+        Some(String::from_utf8(arrangement).unwrap())
+    }
+}
@@ -30,3 +30,98 @@ mod part2 {
             .bench_values(run2)
     }
 }
+
+// The Vec<Vec<Tile>> search used before patterns were bit-packed, comparing
+// cell by cell and transposing to look for vertical mirrors
+mod grid {
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Rocks,
+        Ash,
+    }
+
+    fn transpose<T: Copy>(original: &[Vec<T>]) -> Vec<Vec<T>> {
+        (0..original[0].len())
+            .map(|x| original.iter().map(|row| row[x]).collect())
+            .collect()
+    }
+
+    fn differences(map: &[Vec<Tile>], line: usize, limit: usize) -> Option<usize> {
+        let mut count = 0;
+        for d in 0..line.min(map.len() - line) {
+            for (a, b) in map[line - 1 - d].iter().zip(&map[line + d]) {
+                if a != b {
+                    count += 1;
+                    if count > limit {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(count)
+    }
+
+    fn find_mirror(map: &[Vec<Tile>], smudges: usize) -> Option<usize> {
+        (1..map.len()).find(|&line| differences(map, line, smudges) == Some(smudges))
+    }
+
+    fn summarize(input: &str, smudges: usize) -> usize {
+        input
+            .split("\n\n")
+            .map(|map| {
+                let map = map
+                    .lines()
+                    .map(|line| {
+                        line.bytes()
+                            .map(|b| if b == b'#' { Tile::Rocks } else { Tile::Ash })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                match find_mirror(&map, smudges) {
+                    Some(line) => line * 100,
+                    None => find_mirror(&transpose(&map), smudges).unwrap(),
+                }
+            })
+            .sum()
+    }
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn part1(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(|input| summarize(input, 0))
+    }
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn part2(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(|input| summarize(input, 1))
+    }
+}
+
+mod packed {
+    use super::*;
+    use day13::mirror::{find_reflections, parse_patterns};
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn parse(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(parse_patterns)
+    }
+
+    // The reflection search alone, with patterns already parsed
+    #[divan::bench(args = [0, 1], min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn search(bencher: Bencher, smudges: usize) {
+        let patterns = parse_patterns(include_str!("../input.txt")).unwrap();
+        bencher.bench(|| {
+            patterns
+                .iter()
+                .map(|p| find_reflections(p, smudges).len())
+                .sum::<usize>()
+        })
+    }
+}
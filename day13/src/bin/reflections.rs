@@ -0,0 +1,211 @@
+use day13::mirror::{find_reflections, parse_patterns, Axis};
+
+// Usage: reflections [smudges]
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let smudges = std::env::args()
+        .nth(1)
+        .map_or(0, |a| a.parse::<usize>().unwrap());
+
+    for (i, pattern) in parse_patterns(input).unwrap().iter().enumerate() {
+        let reflections = find_reflections(pattern, smudges);
+        if reflections.is_empty() {
+            println!("Pattern {}: no reflection", i);
+        }
+        for r in reflections {
+            let axis = match r.axis {
+                Axis::Horizontal => "rows",
+                Axis::Vertical => "columns",
+            };
+            let cells = r
+                .smudges
+                .iter()
+                .map(|s| {
+                    format!(
+                        "({},{})~({},{})",
+                        s.cell.0, s.cell.1, s.mirror.0, s.mirror.1
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            println!("Pattern {}: {} {} {}", i, axis, r.line, cells);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day13::mirror::{summarize, MirrorError, Pattern, Smudge, Tile};
+
+    #[test]
+    fn test_example_without_smudges() {
+        let patterns = parse_patterns(include_str!("../../example.txt")).unwrap();
+        let first = find_reflections(&patterns[0], 0);
+        assert_eq!(first.len(), 1);
+        assert_eq!((first[0].axis, first[0].line), (Axis::Vertical, 5));
+        assert!(first[0].smudges.is_empty());
+        let second = find_reflections(&patterns[1], 0);
+        assert_eq!(second.len(), 1);
+        assert_eq!((second[0].axis, second[0].line), (Axis::Horizontal, 4));
+    }
+
+    #[test]
+    fn test_example_smudge_locations() {
+        let patterns = parse_patterns(include_str!("../../example.txt")).unwrap();
+        let first = find_reflections(&patterns[0], 1);
+        assert_eq!((first[0].axis, first[0].line), (Axis::Horizontal, 3));
+        assert_eq!(
+            first[0].smudges,
+            vec![Smudge {
+                cell: (0, 0),
+                mirror: (0, 5)
+            }]
+        );
+        let second = find_reflections(&patterns[1], 1);
+        assert_eq!((second[0].axis, second[0].line), (Axis::Horizontal, 1));
+        assert_eq!(
+            second[0].smudges,
+            vec![Smudge {
+                cell: (4, 0),
+                mirror: (4, 1)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_every_line_is_reported() {
+        // All ash reflects everywhere
+        let pattern = Pattern::parse("...\n...\n").unwrap();
+        let lines = find_reflections(&pattern, 0)
+            .iter()
+            .map(|r| (r.axis, r.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                (Axis::Horizontal, 1),
+                (Axis::Vertical, 1),
+                (Axis::Vertical, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exactly_k_smudges() {
+        let pattern = Pattern::parse("#..#\n....\n").unwrap();
+        // The rows differ in two places, pairs of columns in at most one
+        let two = find_reflections(&pattern, 2);
+        assert_eq!(two.len(), 1);
+        assert_eq!((two[0].axis, two[0].line), (Axis::Horizontal, 1));
+        assert_eq!(two[0].smudges.len(), 2);
+        let one = find_reflections(&pattern, 1)
+            .iter()
+            .map(|r| (r.axis, r.line))
+            .collect::<Vec<_>>();
+        assert_eq!(one, vec![(Axis::Vertical, 1), (Axis::Vertical, 3)]);
+        let clean = find_reflections(&pattern, 0);
+        assert_eq!(clean.len(), 1);
+        assert_eq!((clean[0].axis, clean[0].line), (Axis::Vertical, 2));
+    }
+
+    #[test]
+    fn test_no_reflection_is_an_error() {
+        let input = "##\n..\n\n#.#\n##.\n";
+        assert_eq!(
+            summarize(input, 0),
+            Err(MirrorError::NoReflection {
+                pattern: 1,
+                smudges: 0
+            })
+        );
+        assert!(matches!(
+            summarize("#x\n", 0),
+            Err(MirrorError::Parse { pattern: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_packed_matches_cell_comparison() {
+        // Small xorshift so the patterns are the same on every run
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n) as usize
+        };
+        for _ in 0..300 {
+            let (width, height) = (next(20) + 1, next(20) + 1);
+            let input = (0..height)
+                .map(|_| {
+                    (0..width)
+                        .map(|_| if next(3) == 0 { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let pattern = Pattern::parse(&input).unwrap();
+            let cells = input.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+            for (y, row) in cells.iter().enumerate() {
+                for (x, &c) in row.iter().enumerate() {
+                    let tile = if c == b'#' { Tile::Rocks } else { Tile::Ash };
+                    assert_eq!(pattern.get(x, y), tile);
+                }
+            }
+
+            let smudges = next(3);
+            let mut expected = vec![];
+            for line in 1..height {
+                let diffs = (0..line.min(height - line))
+                    .flat_map(|d| (0..width).map(move |x| (x, d)))
+                    .filter(|&(x, d)| cells[line - 1 - d][x] != cells[line + d][x])
+                    .count();
+                if diffs == smudges {
+                    expected.push((Axis::Horizontal, line));
+                }
+            }
+            for line in 1..width {
+                let diffs = (0..line.min(width - line))
+                    .flat_map(|d| (0..height).map(move |y| (y, d)))
+                    .filter(|&(y, d)| cells[y][line - 1 - d] != cells[y][line + d])
+                    .count();
+                if diffs == smudges {
+                    expected.push((Axis::Vertical, line));
+                }
+            }
+
+            let found = find_reflections(&pattern, smudges);
+            for r in &found {
+                assert_eq!(r.smudges.len(), smudges);
+                for s in &r.smudges {
+                    assert_ne!(
+                        pattern.get(s.cell.0, s.cell.1),
+                        pattern.get(s.mirror.0, s.mirror.1)
+                    );
+                }
+            }
+            let found = found.iter().map(|r| (r.axis, r.line)).collect::<Vec<_>>();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors_name_first_bad_row() {
+        assert_eq!(
+            Pattern::parse("#.#\n##\n#\n"),
+            Err("Row 1 has a different width".to_string())
+        );
+        assert_eq!(
+            Pattern::parse("#.#\n#x#\n#y#\n"),
+            Err("Unknown tile 'x'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pattern_size_limit() {
+        assert!(Pattern::parse(&"#".repeat(128)).is_ok());
+        assert!(Pattern::parse(&"#".repeat(129)).is_err());
+        assert!(Pattern::parse(&"#\n".repeat(129)).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Rocks,
+    Ash,
+}
+
+impl Tile {
+    pub fn from_byte(b: u8) -> Option<Tile> {
+        match b {
+            b'#' => Some(Tile::Rocks),
+            b'.' => Some(Tile::Ash),
+            _ => None,
+        }
+    }
+}
+
+// Rocks packed into one mask per row and one per column, bit x of a row
+// being column x and bit y of a column being row y. Comparing two rows or
+// columns is then an XOR, and its popcount is the number of smudges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    rows: Vec<u128>,
+    columns: Vec<u128>,
+}
+
+impl Pattern {
+    pub fn parse(input: &str) -> Result<Pattern, String> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |l| l.len());
+        if width == 0 {
+            return Err("Empty pattern".to_string());
+        }
+        if width > 128 || lines.len() > 128 {
+            return Err(format!(
+                "Pattern is {}x{}, at most 128x128 is supported",
+                width,
+                lines.len()
+            ));
+        }
+
+        // Checked top to bottom before packing so errors name the first bad row
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(format!("Row {} has a different width", y));
+            }
+            if let Some(b) = line.bytes().find(|&b| Tile::from_byte(b).is_none()) {
+                return Err(format!("Unknown tile {:?}", b as char));
+            }
+        }
+
+        // Tiles are shifted in from the far end, bottom row first, so that
+        // bit 0 is the first tile. Shifting by one is much cheaper than
+        // setting bits at variable offsets in a u128.
+        let mut rows = Vec::with_capacity(lines.len());
+        let mut columns = vec![0; width];
+        for line in lines.iter().rev() {
+            let mut row = 0;
+            for (column, b) in columns.iter_mut().zip(line.bytes()).rev() {
+                let rock = (b == b'#') as u128;
+                row = row << 1 | rock;
+                *column = *column << 1 | rock;
+            }
+            rows.push(row);
+        }
+        rows.reverse();
+
+        Ok(Pattern {
+            width,
+            height: lines.len(),
+            rows,
+            columns,
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Tile {
+        if self.rows[y] >> x & 1 == 1 {
+            Tile::Rocks
+        } else {
+            Tile::Ash
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    // Mirror between two rows
+    Horizontal,
+    // Mirror between two columns
+    Vertical,
+}
+
+// A cell that differs from its reflection, as (x, y) on the top or left side
+// of the mirror and the cell it is reflected onto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Smudge {
+    pub cell: (usize, usize),
+    pub mirror: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    // Rows above or columns left of the mirror
+    pub line: usize,
+    pub smudges: Vec<Smudge>,
+}
+
+impl Reflection {
+    pub fn summary(&self) -> usize {
+        match self.axis {
+            Axis::Horizontal => self.line * 100,
+            Axis::Vertical => self.line,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MirrorError {
+    Parse { pattern: usize, message: String },
+    NoReflection { pattern: usize, smudges: usize },
+}
+
+impl fmt::Display for MirrorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorError::Parse { pattern, message } => {
+                write!(f, "pattern {}: {}", pattern, message)
+            }
+            MirrorError::NoReflection { pattern, smudges } => write!(
+                f,
+                "pattern {} has no reflection with exactly {} smudges",
+                pattern, smudges
+            ),
+        }
+    }
+}
+
+impl From<MirrorError> for String {
+    fn from(e: MirrorError) -> String {
+        e.to_string()
+    }
+}
+
+// Differences across a mirror placed after `line` masks, as (position along
+// the mask, mask before the mirror, mask after it). Gives up once there are
+// more than `limit` of them.
+fn smudges_across(masks: &[u128], line: usize, limit: usize) -> Option<Vec<(usize, usize, usize)>> {
+    let mut smudges = vec![];
+    for d in 0..line.min(masks.len() - line) {
+        let (before, after) = (line - 1 - d, line + d);
+        let mut diff = masks[before] ^ masks[after];
+        if smudges.len() + diff.count_ones() as usize > limit {
+            return None;
+        }
+        while diff != 0 {
+            smudges.push((diff.trailing_zeros() as usize, before, after));
+            diff &= diff - 1;
+        }
+    }
+    Some(smudges)
+}
+
+fn row_smudges(pattern: &Pattern, line: usize, limit: usize) -> Option<Vec<Smudge>> {
+    let smudges = smudges_across(&pattern.rows, line, limit)?;
+    Some(
+        smudges
+            .into_iter()
+            .map(|(x, above, below)| Smudge {
+                cell: (x, above),
+                mirror: (x, below),
+            })
+            .collect(),
+    )
+}
+
+fn column_smudges(pattern: &Pattern, line: usize, limit: usize) -> Option<Vec<Smudge>> {
+    let smudges = smudges_across(&pattern.columns, line, limit)?;
+    Some(
+        smudges
+            .into_iter()
+            .map(|(y, left, right)| Smudge {
+                cell: (left, y),
+                mirror: (right, y),
+            })
+            .collect(),
+    )
+}
+
+// Every mirror line, horizontal ones first, where exactly `smudges` cells
+// differ from their reflection
+pub fn find_reflections(pattern: &Pattern, smudges: usize) -> Vec<Reflection> {
+    let horizontal = (1..pattern.height).filter_map(|line| {
+        row_smudges(pattern, line, smudges)
+            .filter(|s| s.len() == smudges)
+            .map(|s| Reflection {
+                axis: Axis::Horizontal,
+                line,
+                smudges: s,
+            })
+    });
+    let vertical = (1..pattern.width).filter_map(|line| {
+        column_smudges(pattern, line, smudges)
+            .filter(|s| s.len() == smudges)
+            .map(|s| Reflection {
+                axis: Axis::Vertical,
+                line,
+                smudges: s,
+            })
+    });
+    horizontal.chain(vertical).collect()
+}
+
+pub fn parse_patterns(input: &str) -> Result<Vec<Pattern>, MirrorError> {
+    input
+        .split("\n\n")
+        .enumerate()
+        .map(|(i, p)| {
+            Pattern::parse(p).map_err(|message| MirrorError::Parse {
+                pattern: i,
+                message,
+            })
+        })
+        .collect()
+}
+
+// Sum of the summaries of the first reflection in every pattern
+pub fn summarize(input: &str, smudges: usize) -> Result<usize, MirrorError> {
+    parse_patterns(input)?
+        .par_iter()
+        .enumerate()
+        .map(|(i, pattern)| {
+            find_reflections(pattern, smudges)
+                .first()
+                .map(Reflection::summary)
+                .ok_or(MirrorError::NoReflection {
+                    pattern: i,
+                    smudges,
+                })
+        })
+        .sum()
+}
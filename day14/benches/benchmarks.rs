@@ -30,3 +30,48 @@ mod part2 {
             .bench_values(run2)
     }
 }
+
+mod platform {
+    use super::*;
+    use day14::platform::Platform;
+    use day14::schedule::Schedule;
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn spin(bencher: Bencher) {
+        let platform = Platform::parse(include_str!("../input.txt")).unwrap();
+        let schedule = Schedule::parse("NWSE").unwrap();
+        bencher
+            .with_inputs(|| platform.clone())
+            .bench_refs(|platform| schedule.apply(platform))
+    }
+}
+
+// The HashMap platform, run with `--features hashmap`
+#[cfg(feature = "hashmap")]
+mod hashmap {
+    use super::*;
+    use day14::hashmap::{move_stones, parse, run};
+    use day14::platform::Direction;
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn spin(bencher: Bencher) {
+        let (map, rows, cols) = parse(include_str!("../input.txt"));
+        bencher.with_inputs(|| map.clone()).bench_refs(|map| {
+            for direction in [
+                Direction::North,
+                Direction::West,
+                Direction::South,
+                Direction::East,
+            ] {
+                move_stones(map, &direction, &rows, &cols);
+            }
+        })
+    }
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn part2(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(run)
+    }
+}
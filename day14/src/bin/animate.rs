@@ -0,0 +1,129 @@
+use day14::platform::Platform;
+use day14::recorder::Recording;
+use day14::schedule::Schedule;
+use std::time::Duration;
+
+// Usage: animate [platform file] [cycles] [delay in ms] [gif file]
+// Plays the first cycles in the terminal, or writes them to the GIF file
+pub fn main() {
+    let mut args = std::env::args().skip(1);
+    let input = match args.next() {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => include_str!("../../input.txt").to_string(),
+    };
+    let cycles = args.next().map_or(12, |a| a.parse::<usize>().unwrap());
+    let delay = Duration::from_millis(args.next().map_or(150, |a| a.parse::<u64>().unwrap()));
+
+    let platform = Platform::parse(&input).unwrap();
+    let schedule = Schedule::parse("NWSE").unwrap();
+    let recording = Recording::record(&platform, &schedule, cycles);
+
+    match args.next() {
+        Some(path) => {
+            let file = std::fs::File::create(&path).unwrap();
+            recording
+                .write_gif(std::io::BufWriter::new(file), 8, delay)
+                .unwrap();
+            println!("Wrote {} frames to {}", recording.frames.len(), path);
+        }
+        None => recording.play(&mut std::io::stdout(), delay).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day14::platform::Direction;
+    use day14::recorder::Marker;
+    use day14::schedule::Loop;
+
+    fn example(cycles: usize) -> Recording {
+        let platform = Platform::parse(include_str!("../../example.txt")).unwrap();
+        Recording::record(&platform, &Schedule::parse("NWSE").unwrap(), cycles)
+    }
+
+    #[test]
+    fn test_frame_per_tilt() {
+        let recording = example(3);
+        assert_eq!(recording.frames.len(), 1 + 3 * 4);
+        assert_eq!(recording.frames[0].tilt, None);
+        assert_eq!(recording.frames[0].caption(), "start");
+        assert_eq!(recording.frames[5].cycle, 2);
+        assert_eq!(recording.frames[5].tilt, Some(Direction::North));
+        assert_eq!(recording.frames[5].caption(), "cycle 2, tilted North");
+        let mut spun = recording.frames[0].platform.clone();
+        Schedule::parse("NWSE").unwrap().apply(&mut spun);
+        assert_eq!(recording.frames[4].platform, spun);
+    }
+
+    #[test]
+    fn test_loop_markers() {
+        // The example repeats the state after cycle 3 after cycle 10
+        let recording = example(12);
+        assert_eq!(
+            recording.found,
+            Some(Loop {
+                start: 3,
+                period: 7
+            })
+        );
+        let marked = recording
+            .frames
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| f.marker.map(|m| (i, m)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            marked,
+            vec![(12, Marker::LoopStart), (40, Marker::Repeat { of: 3 })]
+        );
+        assert_eq!(recording.frames[12].platform, recording.frames[40].platform);
+        assert_eq!(
+            recording.frames[40].caption(),
+            "cycle 10, tilted East, same as cycle 3"
+        );
+
+        // The repeat is found on the last recorded cycle
+        assert!(example(10).found.is_some());
+        let early = example(9);
+        assert_eq!(early.found, None);
+        assert!(early.frames.iter().all(|f| f.marker.is_none()));
+    }
+
+    #[test]
+    fn test_play() {
+        let recording = example(2);
+        let mut out = vec![];
+        recording.play(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[H").count(), recording.frames.len());
+        assert!(out.contains("cycle 2, tilted East"));
+    }
+
+    #[test]
+    fn test_gif() {
+        let recording = example(10);
+        let mut out = vec![];
+        recording
+            .write_gif(&mut out, 2, Duration::from_millis(100))
+            .unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&out[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (20, 22));
+        // The band above the platform shows the loop markers
+        let mut bands = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            bands.push(frame.buffer[0]);
+        }
+        assert_eq!(bands.len(), recording.frames.len());
+        let marked = bands
+            .iter()
+            .enumerate()
+            .filter(|&(_, &band)| band != bands[1])
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(marked, vec![12, 40]);
+        assert!(recording.write_gif(vec![], 0, Duration::ZERO).is_err());
+        assert!(recording.write_gif(vec![], 10_000, Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use day14::platform::{Direction, Platform};
+use day14::schedule::Schedule;
+
+// Usage: schedule [tilts] [cycles], e.g. schedule ESWN 1000
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let mut args = std::env::args().skip(1);
+    let schedule = Schedule::parse(&args.next().unwrap_or("NWSE".to_string())).unwrap();
+    let cycles = args
+        .next()
+        .map_or(1_000_000_000, |a| a.parse::<usize>().unwrap());
+
+    let mut platform = Platform::parse(input).unwrap();
+    match schedule.run(&mut platform, cycles) {
+        Some(found) => println!(
+            "{} repeats every {} cycles from cycle {}",
+            schedule, found.period, found.start
+        ),
+        None => println!("No repeat within {} cycles of {}", cycles, schedule),
+    }
+    for edge in "NSWE".chars().filter_map(Direction::from_char) {
+        println!("Load on {:?}: {}", edge, platform.load(edge));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day14::schedule::{load_after, Loop};
+
+    // Runs every cycle without looking for a loop
+    fn run_slowly(input: &str, schedule: &str, cycles: usize) -> Platform {
+        let mut platform = Platform::parse(input).unwrap();
+        let schedule = Schedule::parse(schedule).unwrap();
+        for _ in 0..cycles {
+            schedule.apply(&mut platform);
+        }
+        platform
+    }
+
+    #[test]
+    fn test_example_billion_cycles() {
+        let input = include_str!("../../example.txt");
+        assert_eq!(
+            load_after(input, "NWSE", 1_000_000_000, Direction::North),
+            Ok(64)
+        );
+    }
+
+    #[test]
+    fn test_load_on_every_edge() {
+        // One rock in the second row and third column of a 3x4 platform
+        let platform = Platform::parse("...\n..O\n...\n...\n").unwrap();
+        assert_eq!(platform.load(Direction::North), 3);
+        assert_eq!(platform.load(Direction::South), 2);
+        assert_eq!(platform.load(Direction::West), 1);
+        assert_eq!(platform.load(Direction::East), 3);
+        assert_eq!(load_after("O.\n.O\n", "E", 1, Direction::East), Ok(4));
+    }
+
+    #[test]
+    fn test_fast_forward_matches_every_cycle() {
+        let input = include_str!("../../example.txt");
+        for schedule in ["NWSE", "NN", "ESWN", "NE", "SWW"] {
+            for cycles in [0, 1, 2, 3, 7, 10, 25, 64] {
+                let mut platform = Platform::parse(input).unwrap();
+                Schedule::parse(schedule)
+                    .unwrap()
+                    .run(&mut platform, cycles);
+                assert_eq!(
+                    platform,
+                    run_slowly(input, schedule, cycles),
+                    "{} for {} cycles",
+                    schedule,
+                    cycles
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_loops() {
+        let input = include_str!("../../example.txt");
+        let mut platform = Platform::parse(input).unwrap();
+        // Tilting north twice changes nothing the second time
+        let found = Schedule::parse("NN").unwrap().run(&mut platform, 10);
+        assert_eq!(
+            found,
+            Some(Loop {
+                start: 1,
+                period: 1
+            })
+        );
+        let mut platform = Platform::parse(input).unwrap();
+        let found = Schedule::parse("NWSE").unwrap().run(&mut platform, 100);
+        assert_eq!(
+            found,
+            Some(Loop {
+                start: 3,
+                period: 7
+            })
+        );
+        let found = found.unwrap();
+        assert_eq!(found.equivalent(2), 2);
+        assert_eq!(found.equivalent(10), 3);
+        assert_eq!(found.equivalent(1_000_000_000), 6);
+        // Too few cycles to see the repeat
+        let mut platform = Platform::parse(input).unwrap();
+        assert_eq!(Schedule::parse("NWSE").unwrap().run(&mut platform, 5), None);
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let schedule = Schedule::parse("ESWN").unwrap();
+        assert_eq!(
+            schedule.tilts,
+            vec![
+                Direction::East,
+                Direction::South,
+                Direction::West,
+                Direction::North
+            ]
+        );
+        assert_eq!(schedule.to_string(), "ESWN");
+        assert!(Schedule::parse("").is_err());
+        assert!(Schedule::parse("NX").is_err());
+    }
+}
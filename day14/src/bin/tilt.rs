@@ -0,0 +1,144 @@
+use day14::platform::{Direction, Platform};
+
+// Usage: tilt [directions], e.g. tilt NWSE
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let directions = std::env::args().nth(1).unwrap_or("N".to_string());
+    let mut platform = Platform::parse(input).unwrap();
+
+    for c in directions.chars() {
+        platform.tilt(Direction::from_char(c).unwrap());
+    }
+    print!("{}", platform);
+    for edge in "NSWE".chars().filter_map(Direction::from_char) {
+        println!("Load on {:?}: {}", edge, platform.load(edge));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day14::schedule::Schedule;
+
+    fn tilted(input: &str, directions: &str) -> String {
+        let mut platform = Platform::parse(input).unwrap();
+        for c in directions.chars() {
+            platform.tilt(Direction::from_char(c).unwrap());
+        }
+        platform.to_string()
+    }
+
+    #[test]
+    fn test_example_north() {
+        let input = include_str!("../../example.txt");
+        assert_eq!(
+            tilted(input, "N"),
+            include_str!("../../example_north.txt").replace("\r\n", "\n")
+        );
+    }
+
+    #[test]
+    fn test_example_spins() {
+        let mut platform = Platform::parse(include_str!("../../example.txt")).unwrap();
+        let spin = Schedule::parse("NWSE").unwrap();
+        spin.apply(&mut platform);
+        assert_eq!(
+            platform.to_string(),
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+        );
+        spin.apply(&mut platform);
+        spin.apply(&mut platform);
+        assert_eq!(
+            platform.to_string(),
+            ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O
+"
+        );
+    }
+
+    #[test]
+    fn test_every_direction() {
+        let input = "O.#.O\n.O..O\n#.O..\n";
+        assert_eq!(tilted(input, "N"), "OO#.O\n..O.O\n#....\n");
+        assert_eq!(tilted(input, "S"), "..#..\nO...O\n#OO.O\n");
+        assert_eq!(tilted(input, "W"), "O.#O.\nOO...\n#O...\n");
+        assert_eq!(tilted(input, "E"), ".O#.O\n...OO\n#...O\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Platform::parse("").is_err());
+        assert!(Platform::parse("O.\n.\n").is_err());
+        assert!(Platform::parse("Ox\n").is_err());
+        assert!(Platform::parse(&"O".repeat(128)).is_ok());
+        assert!(Platform::parse(&"O".repeat(129)).is_err());
+    }
+
+    #[cfg(feature = "hashmap")]
+    #[test]
+    fn test_matches_hashmap() {
+        use day14::hashmap;
+
+        // Small xorshift so the platforms are the same on every run
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n) as usize
+        };
+        let directions = [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ];
+        for _ in 0..200 {
+            let (width, height) = (next(16) + 1, next(16) + 1);
+            let input = (0..height)
+                .map(|_| {
+                    (0..width)
+                        .map(|_| match next(4) {
+                            0 => 'O',
+                            1 => '#',
+                            _ => '.',
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let mut platform = Platform::parse(&input).unwrap();
+            let (mut map, rows, cols) = hashmap::parse(&input);
+            for _ in 0..8 {
+                let direction = directions[next(4)];
+                platform.tilt(direction);
+                hashmap::move_stones(&mut map, &direction, &rows, &cols);
+                assert_eq!(platform.to_string(), hashmap::render(&map, &rows, &cols));
+                for edge in directions {
+                    assert_eq!(
+                        platform.load(edge),
+                        hashmap::support_weight(&map, &edge, &rows, &cols)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,2 +1,7 @@
+#[cfg(feature = "hashmap")]
+pub mod hashmap;
 pub mod part1;
 pub mod part2;
+pub mod platform;
+pub mod recorder;
+pub mod schedule;
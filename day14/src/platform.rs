@@ -0,0 +1,210 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'N' => Some(Direction::North),
+            'S' => Some(Direction::South),
+            'W' => Some(Direction::West),
+            'E' => Some(Direction::East),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::North => 'N',
+            Direction::South => 'S',
+            Direction::West => 'W',
+            Direction::East => 'E',
+        }
+    }
+}
+
+// The lowest `n` bits set, for n up to 128
+fn low_bits(n: u32) -> u128 {
+    if n == 0 {
+        0
+    } else {
+        u128::MAX >> (128 - n)
+    }
+}
+
+// Rounded rocks and cube rocks packed into one mask per row, bit x being
+// column x. Tilting north or south moves whole rows of rocks at once, tilting
+// west or east counts the rocks in each run of open cells between walls and
+// packs them against one end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Platform {
+    pub width: usize,
+    pub height: usize,
+    rocks: Vec<u128>,
+    walls: Vec<u128>,
+    // The runs of open cells in each row, as masks
+    segments: Vec<Vec<u128>>,
+}
+
+impl Platform {
+    pub fn parse(input: &str) -> Result<Platform, String> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |l| l.len());
+        if width == 0 {
+            return Err("Empty platform".to_string());
+        }
+        if width > 128 {
+            return Err(format!(
+                "Platform is {} wide, at most 128 is supported",
+                width
+            ));
+        }
+
+        let mut rocks = Vec::with_capacity(lines.len());
+        let mut walls = Vec::with_capacity(lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(format!("Row {} has a different width", y));
+            }
+            let (mut row_rocks, mut row_walls) = (0, 0);
+            for (x, b) in line.bytes().enumerate() {
+                match b {
+                    b'O' => row_rocks |= 1 << x,
+                    b'#' => row_walls |= 1 << x,
+                    b'.' => {}
+                    _ => return Err(format!("Unknown tile {:?}", b as char)),
+                }
+            }
+            rocks.push(row_rocks);
+            walls.push(row_walls);
+        }
+
+        let segments = walls
+            .iter()
+            .map(|&row_walls| {
+                let mut open = !row_walls & low_bits(width as u32);
+                let mut segments = vec![];
+                while open != 0 {
+                    let start = open.trailing_zeros();
+                    let segment = low_bits((open >> start).trailing_ones()) << start;
+                    segments.push(segment);
+                    open &= !segment;
+                }
+                segments
+            })
+            .collect();
+
+        Ok(Platform {
+            width,
+            height: lines.len(),
+            rocks,
+            walls,
+            segments,
+        })
+    }
+
+    // The rounded rocks of each row, bit x being column x
+    pub fn rocks(&self) -> &[u128] {
+        &self.rocks
+    }
+
+    pub fn tilt(&mut self, direction: Direction) {
+        match direction {
+            Direction::North => self.tilt_vertical(true),
+            Direction::South => self.tilt_vertical(false),
+            Direction::West => self.tilt_horizontal(true),
+            Direction::East => self.tilt_horizontal(false),
+        }
+    }
+
+    // Each rock adds its distance in rows or columns from the opposite edge,
+    // counting its own
+    pub fn load(&self, edge: Direction) -> usize {
+        match edge {
+            Direction::North | Direction::South => self
+                .rocks
+                .iter()
+                .enumerate()
+                .map(|(y, row)| {
+                    let distance = match edge {
+                        Direction::North => self.height - y,
+                        _ => y + 1,
+                    };
+                    distance * row.count_ones() as usize
+                })
+                .sum(),
+            Direction::West | Direction::East => (0..self.width)
+                .map(|x| {
+                    let distance = match edge {
+                        Direction::West => self.width - x,
+                        _ => x + 1,
+                    };
+                    let rocks = self.rocks.iter().filter(|&&row| row >> x & 1 == 1).count();
+                    distance * rocks
+                })
+                .sum(),
+        }
+    }
+
+    // Rows are settled in the order they reach the edge. Everything between
+    // a row and the edge is already settled, so its rocks move towards the
+    // edge one row at a time until none of them can go any further.
+    fn tilt_vertical(&mut self, north: bool) {
+        let height = self.height;
+        let index = |i: usize| if north { i } else { height - 1 - i };
+        for i in 1..height {
+            let mut moving = self.rocks[index(i)];
+            for k in (1..=i).rev() {
+                let (from, to) = (index(k), index(k - 1));
+                moving &= !(self.rocks[to] | self.walls[to]);
+                if moving == 0 {
+                    break;
+                }
+                self.rocks[from] ^= moving;
+                self.rocks[to] |= moving;
+            }
+        }
+    }
+
+    fn tilt_horizontal(&mut self, west: bool) {
+        for (row, segments) in self.rocks.iter_mut().zip(&self.segments) {
+            let mut tilted = 0;
+            for &segment in segments {
+                let count = (*row & segment).count_ones();
+                if count == 0 {
+                    continue;
+                }
+                let start = segment.trailing_zeros();
+                let offset = if west {
+                    start
+                } else {
+                    start + segment.count_ones() - count
+                };
+                tilted |= low_bits(count) << offset;
+            }
+            *row = tilted;
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (rocks, walls) in self.rocks.iter().zip(&self.walls) {
+            let line = (0..self.width)
+                .map(|x| match (rocks >> x & 1, walls >> x & 1) {
+                    (1, _) => 'O',
+                    (_, 1) => '#',
+                    _ => '.',
+                })
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+use std::io::Write;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::platform::{Direction, Platform};
+use crate::schedule::{Loop, Schedule};
+
+// Where a frame sits relative to the loop found by cycle detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    // The first state that is later repeated
+    LoopStart,
+    // The same state as at the end of cycle `of`, where the repeat was found
+    Repeat { of: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    // Cycles started so far, 0 for the platform before any tilt
+    pub cycle: usize,
+    pub tilt: Option<Direction>,
+    pub platform: Platform,
+    pub marker: Option<Marker>,
+}
+
+impl Frame {
+    pub fn caption(&self) -> String {
+        let mut caption = match self.tilt {
+            Some(tilt) => format!("cycle {}, tilted {:?}", self.cycle, tilt),
+            None => "start".to_string(),
+        };
+        match self.marker {
+            Some(Marker::LoopStart) => caption += ", loop starts",
+            Some(Marker::Repeat { of }) => caption += &format!(", same as cycle {}", of),
+            None => {}
+        }
+        caption
+    }
+}
+
+// The platform after every tilt of the first few cycles of a schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub frames: Vec<Frame>,
+    // The loop, if it shows up within the recorded cycles
+    pub found: Option<Loop>,
+}
+
+impl Recording {
+    pub fn record(platform: &Platform, schedule: &Schedule, cycles: usize) -> Recording {
+        // Running one more cycle compares the state after the last recorded
+        // cycle too
+        let found = schedule.run(&mut platform.clone(), cycles + 1);
+        // Only the state at the end of a cycle is compared, so that is the
+        // frame that gets marked
+        let marker = |cycle: usize| match found {
+            Some(found) if cycle == found.start => Some(Marker::LoopStart),
+            Some(found) if cycle == found.start + found.period => {
+                Some(Marker::Repeat { of: found.start })
+            }
+            _ => None,
+        };
+
+        let mut platform = platform.clone();
+        let mut frames = vec![Frame {
+            cycle: 0,
+            tilt: None,
+            platform: platform.clone(),
+            marker: marker(0),
+        }];
+        for cycle in 1..=cycles {
+            for (i, &tilt) in schedule.tilts.iter().enumerate() {
+                platform.tilt(tilt);
+                let last = i == schedule.tilts.len() - 1;
+                frames.push(Frame {
+                    cycle,
+                    tilt: Some(tilt),
+                    platform: platform.clone(),
+                    marker: if last { marker(cycle) } else { None },
+                });
+            }
+        }
+
+        Recording { frames, found }
+    }
+
+    // A frame drawn for a terminal, with its caption underneath
+    pub fn ansi_frame(frame: &Frame) -> String {
+        let mut output = String::new();
+        for line in frame.platform.to_string().lines() {
+            for c in line.chars() {
+                let tile = match c {
+                    'O' => "O".yellow().bold(),
+                    '#' => "#".normal(),
+                    _ => ".".dimmed(),
+                };
+                output += &tile.to_string();
+            }
+            output.push('\n');
+        }
+        let caption = match frame.marker {
+            Some(Marker::LoopStart) => frame.caption().green().bold(),
+            Some(Marker::Repeat { .. }) => frame.caption().red().bold(),
+            None => frame.caption().normal(),
+        };
+        output += &format!("{}\n", caption);
+        output
+    }
+
+    // Plays the frames in place, clearing the screen first and moving the
+    // cursor back to the top left before each frame
+    pub fn play<W: Write>(&self, out: &mut W, delay: Duration) -> std::io::Result<()> {
+        write!(out, "\x1b[2J")?;
+        for frame in &self.frames {
+            // Clear to the end of the screen in case the caption got shorter
+            write!(out, "\x1b[H{}\x1b[J", Recording::ansi_frame(frame))?;
+            out.flush()?;
+            std::thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    // An animated GIF with each tile drawn as a `scale` pixel square. A band
+    // above the platform turns green on the frame where the loop starts and
+    // red where it repeats.
+    pub fn write_gif<W: Write>(&self, out: W, scale: usize, delay: Duration) -> Result<(), String> {
+        let Some(first) = self.frames.first() else {
+            return Err("Nothing recorded".to_string());
+        };
+        let (columns, rows) = (first.platform.width, first.platform.height + 1);
+        let size = |tiles: usize| {
+            tiles
+                .checked_mul(scale)
+                .and_then(|pixels| u16::try_from(pixels).ok())
+                .filter(|&pixels| pixels > 0)
+                .ok_or_else(|| format!("{} tiles at scale {} do not fit in a GIF", tiles, scale))
+        };
+        let (width, height) = (size(columns)?, size(rows)?);
+
+        // Empty, wall, rock, loop start, repeat
+        let palette = [
+            0x20, 0x20, 0x28, 0x70, 0x70, 0x78, 0xf0, 0xc0, 0x30, 0x30, 0xc0, 0x50, 0xd0, 0x30,
+            0x30,
+        ];
+        let mut encoder = gif::Encoder::new(out, width, height, &palette)
+            .map_err(|e| format!("Cannot write GIF: {}", e))?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| format!("Cannot write GIF: {}", e))?;
+        // GIF delays are in hundredths of a second
+        let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+
+        for frame in &self.frames {
+            let band = match frame.marker {
+                Some(Marker::LoopStart) => 3,
+                Some(Marker::Repeat { .. }) => 4,
+                None => 0,
+            };
+            let text = frame.platform.to_string();
+            let tiles = std::iter::repeat_n(band, columns)
+                .chain(text.bytes().filter(|&b| b != b'\n').map(|b| match b {
+                    b'#' => 1,
+                    b'O' => 2,
+                    _ => 0,
+                }))
+                .collect::<Vec<u8>>();
+            let mut pixels = Vec::with_capacity(width as usize * height as usize);
+            for row in tiles.chunks(columns) {
+                let line = row
+                    .iter()
+                    .flat_map(|&tile| std::iter::repeat_n(tile, scale))
+                    .collect::<Vec<_>>();
+                for _ in 0..scale {
+                    pixels.extend_from_slice(&line);
+                }
+            }
+
+            encoder
+                .write_frame(&gif::Frame {
+                    width,
+                    height,
+                    delay,
+                    buffer: Cow::Owned(pixels),
+                    ..gif::Frame::default()
+                })
+                .map_err(|e| format!("Cannot write GIF: {}", e))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::platform::{Direction, Platform};
+
+// The tilts making up one cycle, e.g. "NWSE"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub tilts: Vec<Direction>,
+}
+
+// The platform after cycle `start + period` is the same as after cycle
+// `start`, and so on forever
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Loop {
+    // The cycle in `start..start + period` whose state matches `cycle`
+    pub fn equivalent(&self, cycle: usize) -> usize {
+        if cycle < self.start {
+            cycle
+        } else {
+            self.start + (cycle - self.start) % self.period
+        }
+    }
+}
+
+impl Schedule {
+    pub fn parse(tilts: &str) -> Result<Schedule, String> {
+        let tilts = tilts
+            .chars()
+            .map(|c| Direction::from_char(c).ok_or_else(|| format!("Unknown tilt {:?}", c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if tilts.is_empty() {
+            return Err("Empty schedule".to_string());
+        }
+        Ok(Schedule { tilts })
+    }
+
+    pub fn apply(&self, platform: &mut Platform) {
+        for &direction in &self.tilts {
+            platform.tilt(direction);
+        }
+    }
+
+    // Runs `cycles` cycles of the schedule. Once a state repeats, whole loops
+    // are skipped and only the cycles left over are run, so this stays cheap
+    // for any count. Returns the loop if it was found along the way.
+    pub fn run(&self, platform: &mut Platform, cycles: usize) -> Option<Loop> {
+        let mut seen = HashMap::new();
+        for cycle in 0..cycles {
+            if let Some(start) = seen.insert(platform.rocks().to_vec(), cycle) {
+                let found = Loop {
+                    start,
+                    period: cycle - start,
+                };
+                for _ in 0..(cycles - cycle) % found.period {
+                    self.apply(platform);
+                }
+                return Some(found);
+            }
+            self.apply(platform);
+        }
+        None
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for direction in &self.tilts {
+            write!(f, "{}", direction.to_char())?;
+        }
+        Ok(())
+    }
+}
+
+// The load on `edge` after `cycles` cycles of `schedule`
+pub fn load_after(
+    input: &str,
+    schedule: &str,
+    cycles: usize,
+    edge: Direction,
+) -> Result<usize, String> {
+    let mut platform = Platform::parse(input)?;
+    Schedule::parse(schedule)?.run(&mut platform, cycles);
+    Ok(platform.load(edge))
+}
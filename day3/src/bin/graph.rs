@@ -0,0 +1,61 @@
+use day3::graph::Schematic;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let schematic = Schematic::parse(input).unwrap();
+    match std::env::args().nth(1).as_deref() {
+        Some("dot") => print!("{}", schematic.to_dot()),
+        Some("json") => println!("{}", schematic.to_json().unwrap()),
+        _ => print!("{}", schematic.render()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let input = include_str!("../../example.txt");
+        let schematic = Schematic::parse(input).unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!(schematic.edges.len(), 8);
+        assert_eq!(schematic.part_sum(), 4361);
+        assert_eq!(schematic.gear_ratio_sum(), 467835);
+
+        let non_parts = schematic
+            .numbers
+            .iter()
+            .filter(|n| !schematic.is_part(n.id))
+            .map(|n| n.value)
+            .collect::<Vec<_>>();
+        assert_eq!(non_parts, vec![114, 58]);
+    }
+
+    #[test]
+    fn test_exports() {
+        let input = include_str!("../../example.txt");
+        let schematic = Schematic::parse(input).unwrap();
+        let dot = schematic.to_dot();
+        assert!(dot.starts_with("graph schematic {"));
+        assert!(dot.contains("n0 -- s0;"));
+        assert!(dot.contains("n1 [label=\"114\\n(0,5)\", style=dashed];"));
+
+        let json: serde_json::Value = serde_json::from_str(&schematic.to_json().unwrap()).unwrap();
+        assert_eq!(json["numbers"][0]["value"], 467);
+        assert_eq!(json["symbols"][0]["symbol"], "*");
+        assert_eq!(json["edges"].as_array().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_matches_part_solutions() {
+        let input = include_str!("../../input.txt");
+        let schematic = Schematic::parse(input).unwrap();
+        assert_eq!(schematic.part_sum(), day3::part1::part1(input).unwrap());
+        assert_eq!(
+            schematic.gear_ratio_sum(),
+            day3::part2::part2(input).unwrap()
+        );
+    }
+}
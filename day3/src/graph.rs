@@ -0,0 +1,229 @@
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NumberNode {
+    pub id: usize,
+    pub value: usize,
+    pub row: usize,
+    pub col: usize,
+    pub len: usize,
+}
+
+impl NumberNode {
+    fn touches(&self, row: usize, col: usize) -> bool {
+        self.row.abs_diff(row) <= 1 && col + 1 >= self.col && col <= self.col + self.len
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolNode {
+    pub id: usize,
+    pub symbol: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Edge {
+    pub number: usize,
+    pub symbol: usize,
+}
+
+// Bipartite graph of the schematic: numbers on one side, symbols on the other,
+// with an edge wherever a number touches a symbol (diagonals included).
+#[derive(Debug, Serialize)]
+pub struct Schematic {
+    pub numbers: Vec<NumberNode>,
+    pub symbols: Vec<SymbolNode>,
+    pub edges: Vec<Edge>,
+    #[serde(skip)]
+    grid: Vec<Vec<char>>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Schematic, String> {
+        let mut numbers: Vec<NumberNode> = vec![];
+        let mut symbols: Vec<SymbolNode> = vec![];
+        let grid: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+
+        for (row, line) in grid.iter().enumerate() {
+            let mut start: Option<usize> = None;
+            for col in 0..=line.len() {
+                match line.get(col) {
+                    Some(c) if c.is_ascii_digit() => {
+                        start.get_or_insert(col);
+                    }
+                    c => {
+                        if let Some(s) = start.take() {
+                            let value = line[s..col]
+                                .iter()
+                                .collect::<String>()
+                                .parse::<usize>()
+                                .map_err(|e| format!("Parsing number failed: {}", e))?;
+                            numbers.push(NumberNode {
+                                id: numbers.len(),
+                                value,
+                                row,
+                                col: s,
+                                len: col - s,
+                            });
+                        }
+                        if let Some(&c) = c {
+                            if c != '.' {
+                                symbols.push(SymbolNode {
+                                    id: symbols.len(),
+                                    symbol: c,
+                                    row,
+                                    col,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut by_row: BTreeMap<usize, Vec<&NumberNode>> = BTreeMap::new();
+        for n in &numbers {
+            by_row.entry(n.row).or_default().push(n);
+        }
+
+        let edges = symbols
+            .iter()
+            .flat_map(|s| {
+                by_row
+                    .range(s.row.saturating_sub(1)..=s.row + 1)
+                    .flat_map(|(_, line)| line.iter())
+                    .filter(|n| n.touches(s.row, s.col))
+                    .map(|n| Edge {
+                        number: n.id,
+                        symbol: s.id,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Ok(Schematic {
+            numbers,
+            symbols,
+            edges,
+            grid,
+        })
+    }
+
+    pub fn neighbours_of_symbol(&self, symbol: usize) -> impl Iterator<Item = &NumberNode> {
+        self.edges
+            .iter()
+            .filter(move |e| e.symbol == symbol)
+            .map(|e| &self.numbers[e.number])
+    }
+
+    pub fn is_part(&self, number: usize) -> bool {
+        self.edges.iter().any(|e| e.number == number)
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberNode> {
+        self.numbers.iter().filter(|n| self.is_part(n.id))
+    }
+
+    // Gears are `*` symbols touching exactly two numbers.
+    pub fn gears(&self) -> Vec<(&SymbolNode, &NumberNode, &NumberNode)> {
+        self.symbols
+            .iter()
+            .filter(|s| s.symbol == '*')
+            .flat_map(|s| {
+                let nearby = self.neighbours_of_symbol(s.id).collect::<Vec<_>>();
+                match nearby[..] {
+                    [a, b] => Some((s, a, b)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn part_sum(&self) -> usize {
+        // A number touching several symbols still counts once per symbol,
+        // same as the part1 solution.
+        self.edges
+            .iter()
+            .map(|e| self.numbers[e.number].value)
+            .sum()
+    }
+
+    pub fn gear_ratio_sum(&self) -> usize {
+        self.gears().iter().map(|(_, a, b)| a.value * b.value).sum()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("graph schematic {\n");
+        out.push_str("    node [shape=box];\n");
+        for n in &self.numbers {
+            out.push_str(&format!(
+                "    n{} [label=\"{}\\n({},{})\"{}];\n",
+                n.id,
+                n.value,
+                n.row,
+                n.col,
+                if self.is_part(n.id) {
+                    ""
+                } else {
+                    ", style=dashed"
+                }
+            ));
+        }
+        for s in &self.symbols {
+            out.push_str(&format!(
+                "    s{} [label=\"{}\\n({},{})\", shape=circle];\n",
+                s.id,
+                match s.symbol {
+                    '"' | '\\' => format!("\\{}", s.symbol),
+                    c => c.to_string(),
+                },
+                s.row,
+                s.col
+            ));
+        }
+        for e in &self.edges {
+            out.push_str(&format!("    n{} -- s{};\n", e.number, e.symbol));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    // Part numbers in green, non-part numbers in red, gears in yellow.
+    pub fn render(&self) -> String {
+        let mut owner: Vec<Vec<Option<usize>>> =
+            self.grid.iter().map(|l| vec![None; l.len()]).collect();
+        for n in &self.numbers {
+            owner[n.row][n.col..n.col + n.len].fill(Some(n.id));
+        }
+        let gears = self
+            .gears()
+            .iter()
+            .map(|(s, _, _)| (s.row, s.col))
+            .collect::<Vec<_>>();
+
+        let mut out = String::new();
+        for (row, line) in self.grid.iter().enumerate() {
+            for (col, c) in line.iter().enumerate() {
+                let c = c.to_string();
+                let cell = match owner[row][col] {
+                    Some(id) if self.is_part(id) => c.green().bold(),
+                    Some(_) => c.red(),
+                    None if gears.contains(&(row, col)) => c.yellow().bold(),
+                    None if c == "." => c.dimmed(),
+                    None => c.cyan(),
+                };
+                out.push_str(&cell.to_string());
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,95 @@
+use day4::ledger::Ledger;
+use day4::scratchcard::parse_cards;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let ledger = Ledger::new(&parse_cards(input).unwrap()).unwrap();
+    print!("{}", ledger.to_csv());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day4::rules::{Capped, Doubling, Linear, NextCards, ScoringRule, Wrapping};
+
+    #[test]
+    fn test_example_input() {
+        let input = include_str!("../../example.txt");
+        let ledger = Ledger::new(&parse_cards(input).unwrap()).unwrap();
+        assert_eq!(ledger.total_points(), 13);
+        assert_eq!(ledger.total_cards(), 30);
+
+        let card4 = &ledger.entries[3];
+        assert_eq!(card4.matches, 1);
+        assert_eq!(card4.instances(), 8);
+        assert_eq!(card4.sources, vec![(1, 1), (2, 2), (3, 4)]);
+
+        let csv = ledger.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("card,matches,points,copies,instances,sources")
+        );
+        assert_eq!(lines.next(), Some("1,4,8,0,1,"));
+        assert_eq!(lines.nth(3), Some("5,0,0,13,14,1:1;3:4;4:8"));
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let input = include_str!("../../input.txt");
+        let cards = parse_cards(input).unwrap();
+        let ledger = Ledger::with_rules(&cards, &Doubling, &NextCards).unwrap();
+        assert_eq!(ledger, Ledger::new(&cards).unwrap());
+        assert_eq!(ledger.total_points(), day4::part1::part1(input).unwrap());
+        assert_eq!(ledger.total_cards(), day4::part2::part2(input).unwrap());
+    }
+
+    #[test]
+    fn test_rule_variants() {
+        let input = include_str!("../../example.txt");
+        let cards = parse_cards(input).unwrap();
+
+        let linear = Ledger::with_rules(&cards, &Linear, &NextCards).unwrap();
+        assert_eq!(linear.total_points(), 4 + 2 + 2 + 1);
+
+        let capped = Ledger::with_rules(&cards, &Doubling, &Capped(1)).unwrap();
+        let instances = capped
+            .entries
+            .iter()
+            .map(|e| e.instances())
+            .collect::<Vec<_>>();
+        assert_eq!(instances, vec![1, 2, 3, 4, 5, 1]);
+
+        // The last cards of the example have no matches, so wrapping only
+        // kicks in once the table is rotated.
+        let wrapping = Ledger::with_rules(&cards, &Doubling, &Wrapping).unwrap();
+        assert_eq!(wrapping.total_cards(), 30);
+        let rotated = [&cards[3..], &cards[..3]].concat();
+        let wrapping = Ledger::with_rules(&rotated, &Doubling, &Wrapping).unwrap();
+        assert_eq!(wrapping.entries[0].card, 4);
+        assert_eq!(wrapping.entries[0].sources, vec![(1, 1), (2, 2), (3, 4)]);
+        // Card 4 is settled first, so its late copies never reach card 5.
+        assert_eq!(wrapping.entries[1].sources, vec![(4, 1), (1, 1), (3, 4)]);
+    }
+
+    #[test]
+    fn test_rejects_large_numbers() {
+        assert!(parse_cards("Card 1: 41 200 | 83 86").is_err());
+    }
+
+    #[test]
+    fn test_too_many_matches() {
+        let numbers = (1..=70)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let cards = parse_cards(&format!("Card 1: {} | {}", numbers, numbers)).unwrap();
+        assert!(Ledger::new(&cards).is_err());
+        assert!(Ledger::with_rules(&cards, &Doubling, &NextCards).is_err());
+
+        let linear = Ledger::with_rules(&cards, &Linear, &NextCards).unwrap();
+        assert_eq!(linear.total_points(), 70);
+        assert_eq!(Doubling.score(64), Some(1 << 63));
+        assert_eq!(Doubling.score(65), None);
+    }
+}
@@ -16,4 +16,22 @@ mod tests {
         let result = part1(input).unwrap();
         assert_eq!(result, 13);
     }
+
+    #[test]
+    fn test_too_many_matches() {
+        let numbers = (1..=70)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = format!("Card 1: {} | {}", numbers, numbers);
+        assert!(part1(&card).is_err());
+
+        let numbers = (1..=64)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = format!("Card 1: {} | {}", numbers, numbers);
+        assert_eq!(part1(&card), Ok(1 << 63));
+        assert!(part1(&format!("{}\n{}", card, card)).is_err());
+    }
 }
@@ -0,0 +1,89 @@
+use crate::rules::{CascadeRule, Doubling, NextCards, ScoringRule};
+use crate::scratchcard::Scratchcard;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub card: usize,
+    pub matches: usize,
+    pub points: usize,
+    // Copies won from earlier cards, not counting the original.
+    pub copies: usize,
+    // (source card, copies of this card it produced)
+    pub sources: Vec<(usize, usize)>,
+}
+
+impl LedgerEntry {
+    pub fn instances(&self) -> usize {
+        1 + self.copies
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn new(cards: &[Scratchcard]) -> Result<Ledger, String> {
+        Self::with_rules(cards, &Doubling, &NextCards)
+    }
+
+    pub fn with_rules(
+        cards: &[Scratchcard],
+        scoring: &impl ScoringRule,
+        cascade: &impl CascadeRule,
+    ) -> Result<Ledger, String> {
+        let mut entries = cards
+            .iter()
+            .map(|card| {
+                Ok(LedgerEntry {
+                    card: card.id,
+                    matches: card.matches(),
+                    points: card.score(scoring)?,
+                    copies: 0,
+                    sources: vec![],
+                })
+            })
+            .collect::<Result<Vec<LedgerEntry>, String>>()?;
+
+        for n in 0..entries.len() {
+            let (card, won) = (entries[n].card, entries[n].instances());
+            for target in cascade.targets(n, entries[n].matches, entries.len()) {
+                entries[target].copies += won;
+                entries[target].sources.push((card, won));
+            }
+        }
+
+        Ok(Ledger { entries })
+    }
+
+    pub fn total_points(&self) -> usize {
+        self.entries.iter().map(|e| e.points).sum()
+    }
+
+    pub fn total_cards(&self) -> usize {
+        self.entries.iter().map(|e| e.instances()).sum()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("card,matches,points,copies,instances,sources\n");
+        for e in &self.entries {
+            let sources = e
+                .sources
+                .iter()
+                .map(|(card, count)| format!("{}:{}", card, count))
+                .collect::<Vec<String>>()
+                .join(";");
+            out.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                e.card,
+                e.matches,
+                e.points,
+                e.copies,
+                e.instances(),
+                sources
+            ));
+        }
+        out
+    }
+}
@@ -1,2 +1,5 @@
+pub mod ledger;
 pub mod part1;
 pub mod part2;
+pub mod rules;
+pub mod scratchcard;
@@ -1,33 +1,12 @@
+use crate::scratchcard::parse_cards;
 use rayon::prelude::*;
-// use std::collections::{BTreeSet, HashMap, HashSet};
 
 pub fn part1(input: &str) -> Result<usize, String> {
-    Ok(input
-        .par_lines()
-        .map(|line| {
-            let (winning_row, my_row) = line
-                .split_once(": ")
-                .expect("There should be :")
-                .1
-                .split_once(" | ")
-                .expect("Row split failed");
-
-            let winning_numbers = winning_row
-                .split_whitespace()
-                .map(|n| n.parse::<usize>().expect("Parse number failed"))
-                .collect::<Vec<usize>>();
-
-            let count = my_row
-                .split_whitespace()
-                .filter(|n| {
-                    winning_numbers.contains(&n.parse::<usize>().expect("Parse number failed"))
-                })
-                .count();
-
-            match count {
-                0 => 0,
-                count => 2usize.pow(count as u32 - 1),
-            }
-        })
-        .sum())
+    parse_cards(input)?
+        .par_iter()
+        .map(|card| card.points())
+        .try_reduce(
+            || 0,
+            |a, b| a.checked_add(b).ok_or("Total points overflow".to_string()),
+        )
 }
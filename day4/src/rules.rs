@@ -0,0 +1,68 @@
+pub trait ScoringRule {
+    // None when the score does not fit in a usize.
+    fn score(&self, matches: usize) -> Option<usize>;
+}
+
+pub trait CascadeRule {
+    // Indices of the cards that receive a copy when the card at `index`
+    // (out of `card_count`) has `matches` winning numbers.
+    fn targets(&self, index: usize, matches: usize, card_count: usize) -> Vec<usize>;
+}
+
+// 1 point for the first match, doubled for every match after that. Points
+// run out of room at 64 matches, well within what the parser accepts.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Doubling;
+
+impl ScoringRule for Doubling {
+    fn score(&self, matches: usize) -> Option<usize> {
+        match matches {
+            0 => Some(0),
+            count => 2usize.checked_pow(u32::try_from(count - 1).ok()?),
+        }
+    }
+}
+
+// 1 point per match.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Linear;
+
+impl ScoringRule for Linear {
+    fn score(&self, matches: usize) -> Option<usize> {
+        Some(matches)
+    }
+}
+
+// Copies of the next `matches` cards, never past the end of the table.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NextCards;
+
+impl CascadeRule for NextCards {
+    fn targets(&self, index: usize, matches: usize, card_count: usize) -> Vec<usize> {
+        (index + 1..card_count.min(index + 1 + matches)).collect()
+    }
+}
+
+// Like `NextCards`, but a card never wins copies of more than the capped number of cards.
+#[derive(Debug, Clone, Copy)]
+pub struct Capped(pub usize);
+
+impl CascadeRule for Capped {
+    fn targets(&self, index: usize, matches: usize, card_count: usize) -> Vec<usize> {
+        NextCards.targets(index, matches.min(self.0), card_count)
+    }
+}
+
+// Copies run past the last card and continue from the first one. A card can
+// never win a copy of itself. Cards are still settled in a single pass, so
+// copies that land on an already settled card are counted but do not cascade.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Wrapping;
+
+impl CascadeRule for Wrapping {
+    fn targets(&self, index: usize, matches: usize, card_count: usize) -> Vec<usize> {
+        (1..=matches.min(card_count.saturating_sub(1)))
+            .map(|offset| (index + offset) % card_count)
+            .collect()
+    }
+}
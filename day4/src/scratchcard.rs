@@ -0,0 +1,62 @@
+use crate::rules::{Doubling, ScoringRule};
+use rayon::prelude::*;
+
+// Card numbers are all below 100, so both sides fit in a single u128 bitset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: usize,
+    pub winning: u128,
+    pub have: u128,
+}
+
+impl Scratchcard {
+    pub fn parse(line: &str) -> Result<Scratchcard, String> {
+        let (card, numbers) = line
+            .split_once(':')
+            .ok_or_else(|| format!("There should be : in {:?}", line))?;
+        let id = card
+            .strip_prefix("Card")
+            .ok_or_else(|| format!("Missing card label in {:?}", line))?
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| format!("Parse card id failed: {}", e))?;
+        let (winning_row, my_row) = numbers
+            .split_once(" | ")
+            .ok_or_else(|| format!("Row split failed in {:?}", line))?;
+
+        Ok(Scratchcard {
+            id,
+            winning: Self::parse_set(winning_row)?,
+            have: Self::parse_set(my_row)?,
+        })
+    }
+
+    fn parse_set(row: &str) -> Result<u128, String> {
+        row.split_whitespace().try_fold(0u128, |set, n| {
+            let n = n
+                .parse::<u32>()
+                .map_err(|e| format!("Parse number failed: {}", e))?;
+            if n >= u128::BITS {
+                return Err(format!("Number {} does not fit the card bitset", n));
+            }
+            Ok(set | 1 << n)
+        })
+    }
+
+    pub fn matches(&self) -> usize {
+        (self.winning & self.have).count_ones() as usize
+    }
+
+    pub fn points(&self) -> Result<usize, String> {
+        self.score(&Doubling)
+    }
+
+    pub fn score(&self, rule: &impl ScoringRule) -> Result<usize, String> {
+        rule.score(self.matches())
+            .ok_or_else(|| format!("Card {} has too many matches to score", self.id))
+    }
+}
+
+pub fn parse_cards(input: &str) -> Result<Vec<Scratchcard>, String> {
+    input.par_lines().map(Scratchcard::parse).collect()
+}
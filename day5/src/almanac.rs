@@ -0,0 +1,291 @@
+use crate::piecewise::{PiecewiseMap, Segment};
+use derive_more::Deref;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+
+#[derive(Deref, Eq, PartialEq, Debug, Clone)]
+struct SortRange(Range<isize>);
+
+impl Ord for SortRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start.cmp(&other.start)
+    }
+}
+
+impl PartialOrd for SortRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug)]
+struct ConversionMap {
+    start: isize,
+    end: isize,
+    diff: isize,
+}
+
+#[derive(Debug)]
+struct RangeSet {
+    ranges: BTreeSet<SortRange>,
+}
+
+impl RangeSet {
+    fn new(ranges: BTreeSet<SortRange>) -> RangeSet {
+        RangeSet { ranges }
+    }
+
+    // Overlapping and touching ranges are joined, so that ranges sharing a
+    // start can't knock each other out of the set.
+    fn from_ranges(ranges: impl IntoIterator<Item = Range<isize>>) -> RangeSet {
+        let mut ranges = ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|r| r.start);
+        let mut joined: Vec<Range<isize>> = vec![];
+        for r in ranges {
+            match joined.last_mut() {
+                Some(last) if last.end >= r.start => last.end = last.end.max(r.end),
+                _ => joined.push(r),
+            }
+        }
+        RangeSet::new(joined.into_iter().map(SortRange).collect())
+    }
+
+    fn merge(&mut self, other: RangeSet) {
+        let ranges = std::mem::take(&mut self.ranges);
+        *self = RangeSet::from_ranges(ranges.into_iter().chain(other.ranges).map(|r| r.0));
+    }
+
+    fn split_off(&mut self, split: isize) -> Self {
+        RangeSet {
+            ranges: match &self.ranges.clone().iter().find(|r| r.contains(&split)) {
+                Some(cutoff) => {
+                    let lrange = SortRange(cutoff.start..split);
+                    let rrange = SortRange(split..cutoff.end);
+                    self.ranges.remove(cutoff);
+                    let mut r = self.ranges.split_off(cutoff);
+                    if !lrange.is_empty() {
+                        self.ranges.insert(lrange);
+                    }
+                    if !rrange.is_empty() {
+                        r.insert(rrange);
+                    }
+                    r
+                }
+                None => self.ranges.split_off(&SortRange(split..split)),
+            },
+        }
+    }
+}
+
+impl ConversionMap {
+    fn parse(input: &str) -> Result<ConversionMap, String> {
+        let params: Vec<isize> = input
+            .split_whitespace()
+            .map(|n| n.parse::<isize>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Parse conversion number failed: {}", e))?;
+        match params[..] {
+            [destination, source, length] => Ok(ConversionMap {
+                start: source,
+                end: source + length,
+                diff: destination - source,
+            }),
+            _ => Err(format!("Conversion should have 3 numbers: {:?}", input)),
+        }
+    }
+
+    fn convert(&self, range: RangeSet) -> (RangeSet, RangeSet) {
+        let mut bottom = range;
+        let mut mid = bottom.split_off(self.start);
+        let top = mid.split_off(self.end);
+        let mapped = RangeSet {
+            ranges: mid
+                .ranges
+                .into_iter()
+                .map(|r| SortRange((r.start + self.diff)..(r.end + self.diff)))
+                .collect::<BTreeSet<SortRange>>(),
+        };
+        bottom.merge(top);
+        (bottom, mapped)
+    }
+}
+
+#[derive(Debug)]
+pub struct ConversionLayer {
+    pub source: String,
+    pub destination: String,
+    maps: Vec<ConversionMap>,
+}
+
+impl ConversionLayer {
+    fn new(input: &str) -> Result<ConversionLayer, String> {
+        let mut iter = input.lines().filter(|l| !l.trim().is_empty());
+        let name = iter.next().ok_or("Should be topic string")?;
+        let (source, destination) = name
+            .strip_suffix(" map:")
+            .and_then(|n| n.split_once("-to-"))
+            .ok_or_else(|| format!("Invalid map header: {:?}", name))?;
+        let maps = iter.map(ConversionMap::parse).collect::<Result<_, _>>()?;
+
+        Ok(ConversionLayer {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            maps,
+        })
+    }
+
+    fn convert(&self, input: RangeSet) -> RangeSet {
+        let mut current = input;
+        let mut output = RangeSet::new(BTreeSet::new());
+        for map in self.maps.iter() {
+            let (remains, mapped) = map.convert(current);
+            output.merge(mapped);
+            current = remains;
+        }
+        current.merge(output);
+        current
+    }
+
+    pub fn to_piecewise(&self) -> PiecewiseMap {
+        PiecewiseMap::from_segments(
+            &self
+                .maps
+                .iter()
+                .map(|m| Segment {
+                    start: m.start,
+                    end: m.end,
+                    diff: m.diff,
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+// The conversion layers keyed by their source category. Every category maps
+// to at most one other, so a valid almanac is a single chain of categories.
+#[derive(Debug)]
+pub struct Almanac {
+    layers: HashMap<String, ConversionLayer>,
+}
+
+impl Almanac {
+    pub fn parse(input: &str) -> Result<Almanac, String> {
+        let mut layers = HashMap::new();
+        for chunk in input.split("\n\n").filter(|c| !c.trim().is_empty()) {
+            let layer = ConversionLayer::new(chunk)?;
+            if layers.contains_key(&layer.source) {
+                return Err(format!("Duplicate map from {}", layer.source));
+            }
+            layers.insert(layer.source.clone(), layer);
+        }
+
+        let destinations = layers
+            .values()
+            .map(|l| l.destination.as_str())
+            .collect::<HashSet<&str>>();
+        let roots = layers
+            .keys()
+            .filter(|source| !destinations.contains(source.as_str()))
+            .collect::<Vec<_>>();
+
+        match roots.len() {
+            0 if layers.is_empty() => Err("Almanac has no maps".to_string()),
+            0 => Err("Almanac maps form a cycle".to_string()),
+            1 => {
+                let almanac = Almanac { layers };
+                // Everything has to hang off the single root, otherwise the
+                // leftover maps loop among themselves.
+                if almanac.categories().len() != almanac.layers.len() + 1 {
+                    return Err("Almanac maps form a cycle".to_string());
+                }
+                Ok(almanac)
+            }
+            _ => {
+                let mut roots = roots.into_iter().cloned().collect::<Vec<_>>();
+                roots.sort();
+                Err(format!(
+                    "Almanac has missing links, chains start at {:?}",
+                    roots
+                ))
+            }
+        }
+    }
+
+    // All categories in conversion order, starting from the root.
+    pub fn categories(&self) -> Vec<&str> {
+        let destinations = self
+            .layers
+            .values()
+            .map(|l| l.destination.as_str())
+            .collect::<HashSet<&str>>();
+        let mut current = match self
+            .layers
+            .keys()
+            .find(|source| !destinations.contains(source.as_str()))
+        {
+            Some(root) => root.as_str(),
+            None => return vec![],
+        };
+        let mut categories = vec![current];
+        while let Some(layer) = self.layers.get(current) {
+            if categories.contains(&layer.destination.as_str()) {
+                break;
+            }
+            current = &layer.destination;
+            categories.push(current);
+        }
+        categories
+    }
+
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&ConversionLayer>, String> {
+        let mut path = vec![];
+        let mut current = from;
+        while current != to {
+            let layer = self
+                .layers
+                .get(current)
+                .ok_or_else(|| format!("No conversion path from {} to {}", from, to))?;
+            path.push(layer);
+            current = &layer.destination;
+        }
+        Ok(path)
+    }
+
+    pub fn convert(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: &[Range<isize>],
+    ) -> Result<Vec<Range<isize>>, String> {
+        let input = RangeSet::from_ranges(ranges.iter().cloned());
+        let output = self
+            .path(from, to)?
+            .into_iter()
+            .fold(input, |current, layer| layer.convert(current));
+
+        Ok(output.ranges.into_iter().map(|r| r.0).collect())
+    }
+
+    // All layers between `from` and `to` squashed into a single map.
+    pub fn compose(&self, from: &str, to: &str) -> Result<PiecewiseMap, String> {
+        Ok(self
+            .path(from, to)?
+            .into_iter()
+            .fold(PiecewiseMap::identity(), |map, layer| {
+                map.then(&layer.to_piecewise())
+            }))
+    }
+
+    pub fn convert_range(
+        &self,
+        from: &str,
+        to: &str,
+        range: Range<isize>,
+    ) -> Result<Vec<Range<isize>>, String> {
+        self.convert(from, to, std::slice::from_ref(&range))
+    }
+}
@@ -0,0 +1,107 @@
+use day5::almanac::Almanac;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let (_, maps) = input.split_once("\n\n").unwrap();
+    let almanac = Almanac::parse(maps).unwrap();
+    println!("{}", almanac.categories().join(" -> "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Almanac {
+        let input = include_str!("../../example.txt");
+        Almanac::parse(input.split_once("\n\n").unwrap().1).unwrap()
+    }
+
+    #[test]
+    fn test_example_input() {
+        let almanac = example();
+        assert_eq!(
+            almanac.categories(),
+            vec![
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ]
+        );
+        assert_eq!(
+            almanac.convert_range("seed", "soil", 79..80).unwrap(),
+            vec![81..82]
+        );
+        assert_eq!(
+            almanac.convert_range("soil", "water", 81..82).unwrap(),
+            vec![81..82]
+        );
+        assert_eq!(
+            almanac.convert_range("seed", "location", 13..14).unwrap(),
+            vec![35..36]
+        );
+        assert_eq!(
+            almanac.convert_range("light", "light", 5..7).unwrap(),
+            vec![5..7]
+        );
+        assert!(almanac.convert_range("location", "seed", 0..1).is_err());
+        assert!(almanac.convert_range("seed", "moon", 0..1).is_err());
+        assert_eq!(
+            almanac
+                .convert("seed", "soil", &[97..99, 0..1, 5..5])
+                .unwrap(),
+            vec![0..1, 50..51, 99..100]
+        );
+    }
+
+    #[test]
+    fn test_convert_shared_starts_and_overlaps() {
+        let almanac = example();
+        // Ranges with the same start must not replace each other
+        assert_eq!(
+            almanac.convert("seed", "soil", &[45..50, 45..55]).unwrap(),
+            vec![45..50, 52..57]
+        );
+        assert_eq!(
+            almanac.convert("seed", "soil", &[10..15, 10..20]).unwrap(),
+            vec![10..20]
+        );
+        // Overlapping seeds are only converted once
+        let converted = almanac
+            .convert("seed", "location", &[79..93, 85..100])
+            .unwrap();
+        let once = almanac.convert_range("seed", "location", 79..100).unwrap();
+        assert_eq!(converted, once);
+    }
+
+    #[test]
+    fn test_other_stage_counts() {
+        let almanac = Almanac::parse("seed-to-location map:\n10 0 5\n").unwrap();
+        assert_eq!(almanac.categories(), vec!["seed", "location"]);
+        assert_eq!(
+            almanac.convert_range("seed", "location", 3..8).unwrap(),
+            vec![5..8, 13..15]
+        );
+    }
+
+    #[test]
+    fn test_rejects_broken_almanacs() {
+        let missing = "seed-to-soil map:\n1 0 1\n\nfertilizer-to-water map:\n1 0 1\n";
+        assert!(Almanac::parse(missing)
+            .unwrap_err()
+            .contains("missing links"));
+
+        let cyclic = "a-to-b map:\n1 0 1\n\nb-to-a map:\n1 0 1\n";
+        assert!(Almanac::parse(cyclic).unwrap_err().contains("cycle"));
+
+        let side_cycle = "seed-to-soil map:\n1 0 1\n\na-to-b map:\n1 0 1\n\nb-to-a map:\n1 0 1\n";
+        assert!(Almanac::parse(side_cycle).unwrap_err().contains("cycle"));
+
+        let duplicate = "seed-to-soil map:\n1 0 1\n\nseed-to-water map:\n1 0 1\n";
+        assert!(Almanac::parse(duplicate).unwrap_err().contains("Duplicate"));
+    }
+}
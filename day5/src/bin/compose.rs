@@ -0,0 +1,188 @@
+use day5::almanac::Almanac;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let (_, maps) = input.split_once("\n\n").unwrap();
+    let almanac = Almanac::parse(maps).unwrap();
+    let map = almanac.compose("seed", "location").unwrap();
+    println!("{}", map);
+    match std::env::args().nth(1).map(|l| l.parse::<isize>()) {
+        Some(Ok(location)) => println!(
+            "Seeds ending up at location {}: {:?}",
+            location,
+            map.preimage(location..(location + 1))
+        ),
+        Some(Err(e)) => println!("Invalid location: {}", e),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day5::piecewise::PiecewiseMap;
+    use proptest::prelude::*;
+    use std::ops::Range;
+
+    fn example() -> Almanac {
+        let input = include_str!("../../example.txt");
+        Almanac::parse(input.split_once("\n\n").unwrap().1).unwrap()
+    }
+
+    #[test]
+    fn test_example_input() {
+        let map = example().compose("seed", "location").unwrap();
+        assert_eq!(map.apply(79), 82);
+        assert_eq!(map.apply(14), 43);
+        assert_eq!(map.apply(55), 86);
+        assert_eq!(map.apply(13), 35);
+        assert_eq!(map.preimage(82..83), vec![79..80]);
+
+        let inverse = map.inverse().unwrap();
+        assert_eq!(inverse.apply(35), 13);
+        assert_eq!(inverse.then(&map), PiecewiseMap::identity());
+
+        let lowest = [79..93, 55..68]
+            .into_iter()
+            .flat_map(|seeds| map.image(seeds))
+            .map(|r| r.start)
+            .min();
+        assert_eq!(lowest, Some(46));
+    }
+
+    #[test]
+    fn test_non_injective_map() {
+        let almanac = Almanac::parse("a-to-b map:\n0 10 5\n").unwrap();
+        let map = almanac.compose("a", "b").unwrap();
+        assert_eq!(map.inverse(), None);
+        assert_eq!(map.preimage(2..4), vec![2..4, 12..14]);
+    }
+
+    fn joined(mut ranges: Vec<Range<isize>>) -> Vec<Range<isize>> {
+        ranges.sort_by_key(|r| r.start);
+        let mut joined: Vec<Range<isize>> = vec![];
+        for r in ranges {
+            match joined.last_mut() {
+                Some(last) if last.end >= r.start => last.end = last.end.max(r.end),
+                _ => joined.push(r),
+            }
+        }
+        joined
+    }
+
+    fn almanac_text(layers: &[Vec<(isize, isize, isize)>]) -> String {
+        layers
+            .iter()
+            .enumerate()
+            .map(|(n, maps)| {
+                let maps = maps
+                    .iter()
+                    .map(|(d, s, l)| format!("{} {} {}\n", d, s, l))
+                    .collect::<String>();
+                format!("c{}-to-c{} map:\n{}", n, n + 1, maps)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Consecutive blocks starting at `base`, moved around within the space
+    // they cover. Each layer is one-to-one by construction.
+    fn permutation_layer() -> impl Strategy<Value = Vec<(isize, isize, isize)>> {
+        (0isize..100, prop::collection::vec(1isize..40, 1..5)).prop_flat_map(|(base, lengths)| {
+            let order = (0..lengths.len()).collect::<Vec<_>>();
+            (Just(base), Just(lengths), Just(order).prop_shuffle()).prop_map(
+                |(base, lengths, order)| {
+                    let starts = lengths
+                        .iter()
+                        .scan(base, |next, &l| {
+                            *next += l;
+                            Some(*next - l)
+                        })
+                        .collect::<Vec<_>>();
+                    let mut destination = base;
+                    order
+                        .into_iter()
+                        .map(|i| {
+                            let map = (destination, starts[i], lengths[i]);
+                            destination += lengths[i];
+                            map
+                        })
+                        .collect()
+                },
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn composition_equals_sequential_application(
+            layers in prop::collection::vec(
+                prop::collection::vec((0isize..200, 0isize..200, 1isize..50), 0..5),
+                1..6,
+            ),
+            start in -20isize..260,
+            length in 1isize..60,
+        ) {
+            let almanac = Almanac::parse(&almanac_text(&layers)).unwrap();
+            let last = format!("c{}", layers.len());
+            let map = almanac.compose("c0", &last).unwrap();
+
+            for x in start..(start + length) {
+                let sequential = almanac.convert_range("c0", &last, x..(x + 1)).unwrap();
+                prop_assert_eq!(sequential, vec![map.apply(x)..(map.apply(x) + 1)]);
+                prop_assert!(map
+                    .preimage(map.apply(x)..(map.apply(x) + 1))
+                    .iter()
+                    .any(|r| r.contains(&x)));
+            }
+
+            let composed = map.image(start..(start + length));
+            prop_assert_eq!(
+                composed.iter().map(|r| r.len()).sum::<usize>(),
+                length as usize
+            );
+            let sequential = almanac
+                .convert_range("c0", &last, start..(start + length))
+                .unwrap();
+            prop_assert_eq!(joined(composed), sequential);
+        }
+
+        #[test]
+        fn inverse_round_trips(
+            layers in prop::collection::vec(permutation_layer(), 1..4),
+            x in -20isize..300,
+        ) {
+            let almanac = Almanac::parse(&almanac_text(&layers)).unwrap();
+            let last = format!("c{}", layers.len());
+            let map = almanac.compose("c0", &last).unwrap();
+            let inverse = map.inverse();
+            prop_assert!(inverse.is_some());
+            let inverse = inverse.unwrap();
+            prop_assert_eq!(inverse.apply(map.apply(x)), x);
+            prop_assert_eq!(map.apply(inverse.apply(x)), x);
+        }
+
+        #[test]
+        fn preimage_lands_on_target(
+            layers in prop::collection::vec(
+                prop::collection::vec((0isize..200, 0isize..200, 1isize..50), 0..5),
+                1..4,
+            ),
+            y in -20isize..260,
+        ) {
+            let almanac = Almanac::parse(&almanac_text(&layers)).unwrap();
+            let last = format!("c{}", layers.len());
+            let map = almanac.compose("c0", &last).unwrap();
+            let seeds = map.preimage(y..(y + 1));
+            for x in seeds.iter().flat_map(|r| r.clone()) {
+                prop_assert_eq!(map.apply(x), y);
+            }
+            // Nothing that lands on `y` is missed either
+            for x in -20isize..260 {
+                if map.apply(x) == y {
+                    prop_assert!(seeds.iter().any(|r| r.contains(&x)));
+                }
+            }
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod almanac;
 pub mod part1;
 pub mod part2;
+pub mod piecewise;
@@ -0,0 +1,193 @@
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: isize,
+    pub end: isize,
+    pub diff: isize,
+}
+
+// A map from isize to isize that shifts each segment by its diff and leaves
+// everything outside the segments alone. Segments are kept sorted, disjoint
+// and non-empty, with no identity segments and no touching segments that
+// share a diff, so two maps doing the same thing compare equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> PiecewiseMap {
+        PiecewiseMap::default()
+    }
+
+    // Builds a map from possibly overlapping segments, earlier ones winning
+    // where they overlap, the same way a conversion layer applies its maps.
+    pub fn from_segments(segments: &[Segment]) -> PiecewiseMap {
+        let mut placed: Vec<Segment> = vec![];
+        for segment in segments {
+            let mut pieces = vec![*segment];
+            for taken in &placed {
+                pieces = pieces
+                    .into_iter()
+                    .flat_map(|p| {
+                        [
+                            Segment {
+                                end: p.end.min(taken.start),
+                                ..p
+                            },
+                            Segment {
+                                start: p.start.max(taken.end),
+                                ..p
+                            },
+                        ]
+                    })
+                    .filter(|p| p.start < p.end)
+                    .collect();
+            }
+            placed.extend(pieces);
+        }
+        Self::normalised(placed)
+    }
+
+    fn normalised(mut segments: Vec<Segment>) -> PiecewiseMap {
+        segments.retain(|s| s.start < s.end && s.diff != 0);
+        segments.sort_by_key(|s| s.start);
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for s in segments {
+            match merged.last_mut() {
+                Some(last) if last.end == s.start && last.diff == s.diff => last.end = s.end,
+                _ => merged.push(s),
+            }
+        }
+        PiecewiseMap { segments: merged }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    // Every piece of the number line, identity gaps included.
+    fn pieces(&self) -> Vec<Segment> {
+        let mut pieces = vec![];
+        let mut at = isize::MIN;
+        for s in &self.segments {
+            if at < s.start {
+                pieces.push(Segment {
+                    start: at,
+                    end: s.start,
+                    diff: 0,
+                });
+            }
+            pieces.push(*s);
+            at = s.end;
+        }
+        if at < isize::MAX {
+            pieces.push(Segment {
+                start: at,
+                end: isize::MAX,
+                diff: 0,
+            });
+        }
+        pieces
+    }
+
+    pub fn apply(&self, value: isize) -> isize {
+        let i = self.segments.partition_point(|s| s.end <= value);
+        match self.segments.get(i) {
+            Some(s) if s.start <= value => value + s.diff,
+            _ => value,
+        }
+    }
+
+    pub fn image(&self, range: Range<isize>) -> Vec<Range<isize>> {
+        let mut out = self
+            .pieces()
+            .into_iter()
+            .filter(|p| p.start < range.end && range.start < p.end)
+            .map(|p| (p.start.max(range.start) + p.diff)..(p.end.min(range.end) + p.diff))
+            .collect::<Vec<_>>();
+        out.sort_by_key(|r| r.start);
+        out
+    }
+
+    // Everything that lands in `range`, as sorted and merged ranges.
+    pub fn preimage(&self, range: Range<isize>) -> Vec<Range<isize>> {
+        let mut found = self
+            .pieces()
+            .into_iter()
+            .filter_map(|p| {
+                let start = p.start.saturating_add(p.diff).max(range.start);
+                let end = p.end.saturating_add(p.diff).min(range.end);
+                (start < end).then(|| (start - p.diff)..(end - p.diff))
+            })
+            .collect::<Vec<_>>();
+        found.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<isize>> = vec![];
+        for r in found {
+            match merged.last_mut() {
+                Some(last) if last.end >= r.start => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        merged
+    }
+
+    // `self` first, then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let next_pieces = next.pieces();
+        let mut segments = vec![];
+        for p in self.pieces() {
+            let (lo, hi) = (p.start + p.diff, p.end + p.diff);
+            for q in next_pieces.iter().filter(|q| q.start < hi && lo < q.end) {
+                segments.push(Segment {
+                    start: lo.max(q.start) - p.diff,
+                    end: hi.min(q.end) - p.diff,
+                    diff: p.diff + q.diff,
+                });
+            }
+        }
+        Self::normalised(segments)
+    }
+
+    // Only maps that are one-to-one over the whole number line have an
+    // inverse that is itself a piecewise map.
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let mut images = self
+            .pieces()
+            .into_iter()
+            .map(|p| Segment {
+                start: p.start + p.diff,
+                end: p.end + p.diff,
+                diff: -p.diff,
+            })
+            .collect::<Vec<_>>();
+        images.sort_by_key(|s| s.start);
+        if images.windows(2).any(|w| w[0].end != w[1].start) {
+            return None;
+        }
+        Some(Self::normalised(images))
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>14} {:>14} {:>14} {:>14}",
+            "from", "to", "maps to", "diff"
+        )?;
+        for s in &self.segments {
+            writeln!(
+                f,
+                "{:>14} {:>14} {:>14} {:>+14}",
+                s.start,
+                s.end - 1,
+                s.start + s.diff,
+                s.diff
+            )?;
+        }
+        write!(f, "(identity elsewhere)")
+    }
+}
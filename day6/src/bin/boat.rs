@@ -0,0 +1,108 @@
+use day6::boat::BoatModel;
+use day6::race::Race;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let boat = BoatModel::default();
+    for race in Race::parse_races(input).unwrap() {
+        let solution = boat.solve(&race);
+        println!(
+            "Race {} ms / {} mm: hold {:?} ({} ways), best hold {} ms goes {} mm, {} mm margin",
+            race.time,
+            race.distance,
+            solution.winning,
+            solution.ways_to_win(),
+            solution.best_hold,
+            solution.best_distance,
+            solution.margin
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(boat: &BoatModel, race: &Race) -> Vec<u128> {
+        (0..=race.time)
+            .filter(|&h| boat.distance(race.time, h).unwrap() > race.distance)
+            .collect()
+    }
+
+    #[test]
+    fn test_example_input() {
+        let input = include_str!("../../example.txt");
+        let races = Race::parse_races(input).unwrap();
+        let solution = BoatModel::default().solve(&races[0]);
+        assert_eq!(solution.winning, vec![2..=5]);
+        assert_eq!(solution.best_hold, 3);
+        assert_eq!(solution.best_distance, 12);
+        assert_eq!(solution.margin, 3);
+
+        let solution = BoatModel::default().solve(&races[2]);
+        assert_eq!(solution.winning, vec![11..=19]);
+        assert_eq!(solution.best_hold, 15);
+        assert_eq!(solution.margin, 25);
+    }
+
+    #[test]
+    fn test_default_model_matches_race_solver() {
+        let input = include_str!("../../input.txt");
+        let boat = BoatModel::default();
+        let mut races = Race::parse_races(input).unwrap();
+        races.push(Race::parse_race(input).unwrap());
+        races.push(Race {
+            time: (1 << 64) + 5,
+            distance: 1 << 126,
+        });
+        for race in races {
+            assert_eq!(boat.solve(&race).ways_to_win(), race.count_winning());
+        }
+    }
+
+    #[test]
+    fn test_models_against_brute_force() {
+        let boats = [
+            BoatModel::default(),
+            BoatModel {
+                acceleration: 3,
+                ..Default::default()
+            },
+            BoatModel {
+                max_speed: Some(4),
+                ..Default::default()
+            },
+            BoatModel {
+                acceleration: 2,
+                max_speed: Some(7),
+                charge_cost: 3,
+            },
+            BoatModel {
+                acceleration: 0,
+                ..Default::default()
+            },
+        ];
+        for boat in &boats {
+            for time in 0..25 {
+                for distance in 0..60 {
+                    let race = Race { time, distance };
+                    let solution = boat.solve(&race);
+                    let expected = brute_force(boat, &race);
+                    let found = solution
+                        .winning
+                        .iter()
+                        .flat_map(|r| r.clone())
+                        .collect::<Vec<_>>();
+                    assert_eq!(found, expected, "{:?} {:?}", boat, race);
+
+                    let best = (0..=time)
+                        .map(|h| boat.distance(time, h).unwrap())
+                        .max()
+                        .unwrap();
+                    assert_eq!(solution.best_distance, best, "{:?} {:?}", boat, race);
+                    assert_eq!(solution.margin, best.saturating_sub(distance));
+                }
+            }
+        }
+    }
+}
@@ -16,4 +16,35 @@ mod tests {
         let result = run(input).unwrap();
         assert_eq!(result, 288);
     }
+
+    #[test]
+    fn test_perfect_square_races() {
+        use day6::race::Race;
+
+        // 30^2 - 4 * 200 = 10^2, so holding 10 or 20 only ties the record.
+        let race = Race {
+            time: 30,
+            distance: 200,
+        };
+        assert_eq!(race.first_winning_hold(), Some(11));
+        assert_eq!(race.count_winning(), 9);
+
+        let tie = Race {
+            time: 4,
+            distance: 4,
+        };
+        assert_eq!(tie.count_winning(), 0);
+
+        let cases = [
+            (8, 12, 3),
+            (8, 15, 1),
+            (8, 16, 0),
+            (0, 0, 0),
+            (1, 0, 0),
+            (2, 0, 1),
+        ];
+        for (time, distance, expected) in cases {
+            assert_eq!(Race { time, distance }.count_winning(), expected);
+        }
+    }
 }
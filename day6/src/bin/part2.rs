@@ -16,4 +16,36 @@ mod tests {
         let result = run(input).unwrap();
         assert_eq!(result, 71503);
     }
+
+    #[test]
+    fn test_huge_races() {
+        use day6::race::Race;
+
+        // Past 2^53 the old f64 solution could no longer see single holds.
+        let race = Race {
+            time: (1 << 53) + 1,
+            distance: (1 << 104) / 4 - 7,
+        };
+        assert_eq!(race.count_winning(), 7800463371553964);
+
+        let race = Race {
+            time: (1 << 64) + 5,
+            distance: 1 << 126,
+        };
+        assert_eq!(race.count_winning(), 13581879132);
+
+        let race = run("Time: 123456789 012345678 901234567 890\nDistance: 1000000000000 0000000000000 0000000000000\n");
+        assert_eq!(race, Ok(123456789012345678899614567875));
+
+        let race = Race {
+            time: 300000000000000000000000000000000000000,
+            distance: 200000000000000000000000000000000000000,
+        };
+        assert_eq!(
+            race.count_winning(),
+            299999999999999999999999999999999999999
+        );
+
+        assert!(run("Time: 1\nDistance: 340282366920938463463374607431768211456\n").is_err());
+    }
 }
@@ -0,0 +1,132 @@
+use crate::race::Race;
+use std::ops::RangeInclusive;
+
+// How a boat turns button time into speed. The first `charge_cost` ms of a
+// hold only charge the boat, every ms after that adds `acceleration` mm/ms of
+// speed, up to `max_speed` if there is one. The puzzle boat is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoatModel {
+    pub acceleration: u128,
+    pub max_speed: Option<u128>,
+    pub charge_cost: u128,
+}
+
+impl Default for BoatModel {
+    fn default() -> Self {
+        BoatModel {
+            acceleration: 1,
+            max_speed: None,
+            charge_cost: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub winning: Vec<RangeInclusive<u128>>,
+    pub best_hold: u128,
+    // Saturates at u128::MAX for boats that leave the number range.
+    pub best_distance: u128,
+    // How far past the record the best hold goes, 0 if it doesn't win.
+    pub margin: u128,
+}
+
+impl Solution {
+    pub fn ways_to_win(&self) -> u128 {
+        self.winning.iter().map(|r| r.end() - r.start() + 1).sum()
+    }
+}
+
+impl BoatModel {
+    pub fn speed(&self, hold: u128) -> u128 {
+        let speed = hold
+            .saturating_sub(self.charge_cost)
+            .saturating_mul(self.acceleration);
+        match self.max_speed {
+            Some(max) => speed.min(max),
+            None => speed,
+        }
+    }
+
+    // None when the distance doesn't fit in a u128.
+    pub fn distance(&self, time: u128, hold: u128) -> Option<u128> {
+        self.speed(hold).checked_mul(time.saturating_sub(hold))
+    }
+
+    fn beats(&self, race: &Race, hold: u128) -> bool {
+        hold <= race.time
+            && self
+                .distance(race.time, hold)
+                .is_none_or(|d| d > race.distance)
+    }
+
+    fn further(&self, time: u128, a: u128, b: u128) -> bool {
+        match (self.distance(time, a), self.distance(time, b)) {
+            (None, None) | (Some(_), None) => false,
+            (None, Some(_)) => true,
+            (Some(a), Some(b)) => a > b,
+        }
+    }
+
+    // The distance rises until the boat either hits its top speed or runs
+    // out of time to use it, then falls, so there is a single peak.
+    fn best_hold(&self, time: u128) -> u128 {
+        let c = self.charge_cost.min(time);
+        let peak = c + (time - c) / 2;
+        let mut candidates = vec![c, peak, (peak + 1).min(time), time];
+        if let Some(max) = self.max_speed {
+            if self.acceleration > 0 {
+                let capped = c.saturating_add(max.div_ceil(self.acceleration));
+                candidates.extend([capped.saturating_sub(1), capped].map(|h| h.min(time)));
+            }
+        }
+        candidates.sort();
+        candidates
+            .into_iter()
+            .reduce(|best, h| if self.further(time, h, best) { h } else { best })
+            .unwrap_or(0)
+    }
+
+    // Smallest hold in lo..=hi that gives `wins(hold) == target`, assuming
+    // `wins` flips only once over that range.
+    fn search(lo: u128, hi: u128, target: bool, wins: impl Fn(u128) -> bool) -> u128 {
+        let (mut lo, mut hi) = (lo, hi);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if wins(mid) == target {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        lo
+    }
+
+    pub fn solve(&self, race: &Race) -> Solution {
+        let best_hold = self.best_hold(race.time);
+        let best_distance = self.distance(race.time, best_hold).unwrap_or(u128::MAX);
+
+        if !self.beats(race, best_hold) {
+            return Solution {
+                winning: vec![],
+                best_hold,
+                best_distance,
+                margin: 0,
+            };
+        }
+
+        let wins = |hold| self.beats(race, hold);
+        let first = Self::search(0, best_hold, true, wins);
+        let last = match Self::search(best_hold, race.time, false, wins) {
+            end if wins(end) => end,
+            end => end - 1,
+        };
+
+        Solution {
+            winning: vec![first..=last],
+            best_hold,
+            best_distance,
+            margin: best_distance - race.distance,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Holding the button for `h` ms out of `time` covers `h * (time - h)` mm, so
+// the winning holds are the integers strictly between the two roots of
+// `h^2 - time * h + distance`. Everything is worked out in integers so huge
+// races and perfect-square discriminants come out exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u128,
+    pub distance: u128,
+}
+
+fn parse_number(n: &str) -> Result<u128, String> {
+    n.parse::<u128>()
+        .map_err(|e| format!("Parse number {:?} failed: {}", n, e))
+}
+
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above never undershoots the floor root.
+    let mut x = 1u128 << (n.ilog2() / 2 + 1);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+impl Race {
+    pub fn parse_races(input: &str) -> Result<Vec<Race>, String> {
+        let mut iter = input.lines().map(|line| line.split_whitespace().skip(1));
+        let times = iter.next().ok_or("Expected a time line")?;
+        let distances = iter.next().ok_or("Expected a distance line")?;
+
+        std::iter::zip(times, distances)
+            .map(|(t, d)| {
+                Ok(Race {
+                    time: parse_number(t)?,
+                    distance: parse_number(d)?,
+                })
+            })
+            .collect()
+    }
+
+    // The numbers on each line with the spaces taken out.
+    pub fn parse_race(input: &str) -> Result<Race, String> {
+        let mut iter = input.lines().map(|line| {
+            line.chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect::<String>()
+        });
+
+        Ok(Race {
+            time: parse_number(&iter.next().ok_or("Expected a time line")?)?,
+            distance: parse_number(&iter.next().ok_or("Expected a distance line")?)?,
+        })
+    }
+
+    pub fn beats(&self, hold: u128) -> bool {
+        match hold.checked_mul(self.time.saturating_sub(hold)) {
+            Some(travelled) => hold <= self.time && travelled > self.distance,
+            // Anything that overflows u128 is further than any u128 record.
+            None => true,
+        }
+    }
+
+    // The shortest winning hold, if any hold wins at all.
+    pub fn first_winning_hold(&self) -> Option<u128> {
+        let half = self.time / 2;
+        let guess = match self
+            .time
+            .checked_mul(self.time)
+            .zip(self.distance.checked_mul(4))
+        {
+            Some((tt, dd)) if tt < dd => return None,
+            Some((tt, dd)) => (self.time - isqrt(tt - dd)) / 2,
+            None => self.binary_search(half),
+        };
+
+        // The root guess is off by at most one either way.
+        let mut hold = guess;
+        while hold > 0 && self.beats(hold - 1) {
+            hold -= 1;
+        }
+        while hold <= half && !self.beats(hold) {
+            hold += 1;
+        }
+        (hold <= half).then_some(hold)
+    }
+
+    fn binary_search(&self, half: u128) -> u128 {
+        let (mut lo, mut hi) = (0, half + 1);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.beats(mid) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        lo
+    }
+
+    pub fn count_winning(&self) -> u128 {
+        match self.first_winning_hold() {
+            Some(hold) => self.time - 2 * hold + 1,
+            None => 0,
+        }
+    }
+}
@@ -16,4 +16,48 @@ mod tests {
         let result = run(input).unwrap();
         assert_eq!(result, 5905);
     }
+
+    #[test]
+    fn test_rule_variants() {
+        use day7::camel::{CamelCards, Combination};
+
+        let four = CamelCards::new("23456789TJQKA", "J", 4).unwrap();
+        let hands = four
+            .ranked_hands("2345 1\nJJ23 2\n3322 3\nAJAA 4\n")
+            .unwrap();
+        let signatures = hands
+            .iter()
+            .map(|h| h.combination.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            signatures,
+            vec![
+                Combination(vec![1, 1, 1, 1]),
+                Combination(vec![2, 2]),
+                Combination(vec![3, 1]),
+                Combination(vec![4]),
+            ]
+        );
+        assert_eq!(signatures[1].name(), "Two pair");
+        assert_eq!(
+            four.winnings("2345 1\nJJ23 2\n3322 3\nAJAA 4\n"),
+            Ok(1 + 6 + 6 + 16)
+        );
+
+        let six = CamelCards::new("23456789TJQKA", "2J", 6).unwrap();
+        let hand = six.parse_hand("KK2QQJ 10").unwrap();
+        assert_eq!(hand.combination, Combination(vec![4, 2]));
+        assert_eq!(hand.combination.name(), "4+2");
+        let hand = six.parse_hand("2J22JJ 10").unwrap();
+        assert_eq!(hand.combination, Combination(vec![6]));
+        assert_eq!(hand.combination.name(), "6 of a kind");
+        let hand = six.parse_hand("AAAKKK 10").unwrap();
+        assert_eq!(hand.combination.name(), "3+3");
+        assert!(hand < six.parse_hand("AAAAKQ 1").unwrap());
+
+        assert!(six.parse_hand("AAAAA 1").is_err());
+        assert!(six.parse_hand("AAAAAX 1").is_err());
+        assert!(CamelCards::new("23456789TJQKA", "X", 5).is_err());
+        assert!(CamelCards::new("2345A6789TJQKA", "", 5).is_err());
+    }
 }
@@ -0,0 +1,61 @@
+use day7::camel::CamelCards;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let rules = match std::env::args().nth(1).as_deref() {
+        Some("jokers") => CamelCards::jokers(),
+        _ => CamelCards::standard(),
+    };
+    println!("{}", rules.explain(input).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day7::camel::Tiebreak;
+
+    #[test]
+    fn test_example_input() {
+        let input = include_str!("../../example.txt");
+        let report = CamelCards::jokers().explain(input).unwrap();
+        assert_eq!(report.total(), 5905);
+
+        let hands = report
+            .rows
+            .iter()
+            .map(|r| (r.rank, r.resolved.as_str(), r.winnings, r.tiebreak))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hands,
+            vec![
+                (1, "32T3K", 765, Tiebreak::Weakest),
+                (2, "KK677", 56, Tiebreak::Combination),
+                (3, "T5555", 2052, Tiebreak::Combination),
+                (4, "QQQQA", 1932, Tiebreak::Card(0)),
+                (5, "KTTTT", 1100, Tiebreak::Card(0)),
+            ]
+        );
+        assert_eq!(report.rows[4].substitute.map(|c| c.symbol), Some('T'));
+        assert_eq!(report.rows[0].substitute, None);
+
+        let table = report.to_string();
+        let mut lines = table.lines();
+        assert!(lines.next().unwrap().starts_with(" rank  hand "));
+        assert_eq!(
+            lines.nth(4).unwrap(),
+            "    5  KTJJT  KTTTT  4 of a kind          T     220        1100  card 1"
+        );
+        assert_eq!(lines.next().unwrap(), "Total winnings: 5905");
+    }
+
+    #[test]
+    fn test_matches_winnings() {
+        let input = include_str!("../../input.txt");
+        for rules in [CamelCards::standard(), CamelCards::jokers()] {
+            assert_eq!(
+                rules.explain(input).unwrap().total(),
+                rules.winnings(input).unwrap()
+            );
+        }
+    }
+}
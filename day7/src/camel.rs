@@ -0,0 +1,312 @@
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(PartialOrd, PartialEq, Ord, Eq, Clone, Debug, Copy)]
+pub struct Card {
+    pub rank: usize,
+    pub symbol: char,
+}
+
+// Sizes of the groups of equal cards in a hand, largest first, with any
+// wildcards thrown in with the largest group. Comparing these signatures
+// lexicographically gives the usual order for any hand size, e.g. for five
+// cards [5] > [4, 1] > [3, 2] > [3, 1, 1] > [2, 2, 1] > [2, 1, 1, 1].
+#[derive(PartialOrd, PartialEq, Ord, Eq, Clone, Debug)]
+pub struct Combination(pub Vec<usize>);
+
+impl Combination {
+    pub fn parse(cards: &[Card], wildcards: &[char]) -> Combination {
+        Self::resolve(cards, wildcards).0
+    }
+
+    // Also returns the card the wildcards stand in for, if there are any.
+    // That is the card of the largest group, the strongest one on ties.
+    pub fn resolve(cards: &[Card], wildcards: &[char]) -> (Combination, Option<Card>) {
+        let mut counts: Vec<(Card, usize)> = vec![];
+        for card in cards.iter().filter(|c| !wildcards.contains(&c.symbol)) {
+            match counts.iter_mut().find(|(c, _)| c == card) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*card, 1)),
+            }
+        }
+        counts.sort_by(|(a, m), (b, n)| n.cmp(m).then(b.cmp(a)));
+        let mut signature = counts.iter().map(|(_, n)| *n).collect::<Vec<_>>();
+
+        let wild = cards.len() - signature.iter().sum::<usize>();
+        let substitute = match signature.first_mut() {
+            Some(largest) if wild > 0 => {
+                *largest += wild;
+                Some(counts[0].0)
+            }
+            Some(_) => None,
+            None if wild > 0 => {
+                signature.push(wild);
+                cards.iter().max().copied()
+            }
+            None => None,
+        };
+        (Combination(signature), substitute)
+    }
+
+    pub fn name(&self) -> String {
+        match self.0[..] {
+            [n] => format!("{} of a kind", n),
+            [1, ..] => "High card".to_string(),
+            [2, 1, ..] => "One pair".to_string(),
+            [2, 2, 1] | [2, 2] => "Two pair".to_string(),
+            [3, 2] => "Full house".to_string(),
+            [n, 1, ..] if self.0[1..].iter().all(|&c| c == 1) => format!("{} of a kind", n),
+            _ => self
+                .0
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join("+"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hand {
+    pub cards: Vec<Card>,
+    pub combination: Combination,
+    pub bid: usize,
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.combination
+            .cmp(&other.combination)
+            .then_with(|| self.cards.cmp(&other.cards))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cards == other.cards
+    }
+}
+
+impl Eq for Hand {}
+
+// What put a hand above the hand ranked just below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tiebreak {
+    Weakest,
+    Combination,
+    // Index of the first card that differs.
+    Card(usize),
+    Equal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub hand: Hand,
+    pub rank: usize,
+    pub winnings: usize,
+    pub substitute: Option<Card>,
+    // The hand with every wildcard swapped for the substitute.
+    pub resolved: String,
+    pub tiebreak: Tiebreak,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub rows: Vec<Explanation>,
+}
+
+impl Report {
+    pub fn total(&self) -> usize {
+        self.rows.iter().map(|r| r.winnings).sum()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|r| r.hand.cards.len())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+        writeln!(
+            f,
+            "{:>5}  {:<width$}  {:<width$}  {:<16}  {:>4}  {:>6}  {:>10}  decided by",
+            "rank", "hand", "as", "combination", "via", "bid", "winnings"
+        )?;
+        for r in &self.rows {
+            let hand = r.hand.cards.iter().map(|c| c.symbol).collect::<String>();
+            let via = r
+                .substitute
+                .map_or("-".to_string(), |c| c.symbol.to_string());
+            let decided = match r.tiebreak {
+                Tiebreak::Weakest => "-".to_string(),
+                Tiebreak::Combination => "combination".to_string(),
+                Tiebreak::Card(i) => format!("card {}", i + 1),
+                Tiebreak::Equal => "equal".to_string(),
+            };
+            writeln!(
+                f,
+                "{:>5}  {:<width$}  {:<width$}  {:<16}  {:>4}  {:>6}  {:>10}  {}",
+                r.rank,
+                hand,
+                r.resolved,
+                r.hand.combination.name(),
+                via,
+                r.hand.bid,
+                r.winnings,
+                decided
+            )?;
+        }
+        write!(f, "Total winnings: {}", self.total())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CamelCards {
+    // Weakest card first.
+    ranking: Vec<char>,
+    wildcards: Vec<char>,
+    hand_size: usize,
+}
+
+impl CamelCards {
+    pub fn new(ranking: &str, wildcards: &str, hand_size: usize) -> Result<CamelCards, String> {
+        let ranking = ranking.chars().collect::<Vec<char>>();
+        let wildcards = wildcards.chars().collect::<Vec<char>>();
+        if let Some(c) = ranking
+            .iter()
+            .find(|c| ranking.iter().filter(|r| r == c).count() > 1)
+        {
+            return Err(format!("Card {} is ranked twice", c));
+        }
+        if let Some(c) = wildcards.iter().find(|c| !ranking.contains(c)) {
+            return Err(format!("Wildcard {} is not a ranked card", c));
+        }
+        if hand_size == 0 {
+            return Err("Hands need at least one card".to_string());
+        }
+        Ok(CamelCards {
+            ranking,
+            wildcards,
+            hand_size,
+        })
+    }
+
+    pub fn standard() -> CamelCards {
+        Self::new("23456789TJQKA", "", 5).unwrap()
+    }
+
+    // J is a joker that counts as anything but is the weakest card on ties.
+    pub fn jokers() -> CamelCards {
+        Self::new("J23456789TQKA", "J", 5).unwrap()
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn wildcards(&self) -> &[char] {
+        &self.wildcards
+    }
+
+    pub fn card(&self, symbol: char) -> Result<Card, String> {
+        self.ranking
+            .iter()
+            .position(|&c| c == symbol)
+            .map(|rank| Card { rank, symbol })
+            .ok_or_else(|| format!("Unknown card type {}", symbol))
+    }
+
+    pub fn parse_hand(&self, input: &str) -> Result<Hand, String> {
+        let (cards, bid) = input
+            .split_once(' ')
+            .ok_or("There should be cards and bid")?;
+        let cards = cards
+            .chars()
+            .map(|c| self.card(c))
+            .collect::<Result<Vec<Card>, String>>()?;
+        if cards.len() != self.hand_size {
+            return Err(format!(
+                "There should be exactly {} cards, got {}",
+                self.hand_size,
+                cards.len()
+            ));
+        }
+        let bid = bid
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| format!("Bid should be a number: {}", e))?;
+
+        Ok(Hand {
+            combination: Combination::parse(&cards, &self.wildcards),
+            cards,
+            bid,
+        })
+    }
+
+    // Weakest hand first.
+    pub fn ranked_hands(&self, input: &str) -> Result<Vec<Hand>, String> {
+        let mut hands = input
+            .par_lines()
+            .map(|line| self.parse_hand(line))
+            .collect::<Result<Vec<Hand>, String>>()?;
+        hands.par_sort();
+        Ok(hands)
+    }
+
+    pub fn explain(&self, input: &str) -> Result<Report, String> {
+        let hands = self.ranked_hands(input)?;
+        let rows = hands
+            .iter()
+            .enumerate()
+            .map(|(n, hand)| {
+                let (_, substitute) = Combination::resolve(&hand.cards, &self.wildcards);
+                let resolved = hand
+                    .cards
+                    .iter()
+                    .map(|c| match substitute {
+                        Some(s) if self.wildcards.contains(&c.symbol) => s.symbol,
+                        _ => c.symbol,
+                    })
+                    .collect();
+                let tiebreak = match n.checked_sub(1).map(|p| &hands[p]) {
+                    None => Tiebreak::Weakest,
+                    Some(prev) if prev.combination != hand.combination => Tiebreak::Combination,
+                    Some(prev) => {
+                        match prev.cards.iter().zip(&hand.cards).position(|(a, b)| a != b) {
+                            Some(i) => Tiebreak::Card(i),
+                            None => Tiebreak::Equal,
+                        }
+                    }
+                };
+                Explanation {
+                    hand: hand.clone(),
+                    rank: n + 1,
+                    winnings: (n + 1) * hand.bid,
+                    substitute,
+                    resolved,
+                    tiebreak,
+                }
+            })
+            .collect();
+        Ok(Report { rows })
+    }
+
+    pub fn winnings(&self, input: &str) -> Result<usize, String> {
+        Ok(self
+            .ranked_hands(input)?
+            .iter()
+            .enumerate()
+            .map(|(n, hand)| (n + 1) * hand.bid)
+            .sum())
+    }
+}
@@ -1,2 +1,3 @@
+pub mod camel;
 pub mod part1;
 pub mod part2;
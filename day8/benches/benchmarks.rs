@@ -6,9 +6,11 @@ fn main() {
     divan::main();
 }
 
+use day8::ghost::{first_common_hit, GhostCycle};
 use day8::part1::run as run1;
 use day8::part2::run as run2;
 use divan::Bencher;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
 mod part1 {
@@ -19,6 +21,13 @@ mod part1 {
             .with_inputs(|| include_str!("../input.txt"))
             .bench_values(run1)
     }
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn string_map(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(string_keyed::run1)
+    }
 }
 
 mod part2 {
@@ -29,4 +38,71 @@ mod part2 {
             .with_inputs(|| include_str!("../input.txt"))
             .bench_values(run2)
     }
+
+    #[divan::bench(min_time = Duration::from_millis(500), max_time = Duration::from_secs(10))]
+    fn string_map(bencher: Bencher) {
+        bencher
+            .with_inputs(|| include_str!("../input.txt"))
+            .bench_values(string_keyed::run2)
+    }
+}
+
+// The walks as they were before the arena, looking up every step by name.
+mod string_keyed {
+    use super::*;
+
+    type Nodes = Vec<(String, (String, String))>;
+
+    fn parse(input: &str) -> (&str, Nodes) {
+        let mut it = input.lines();
+        let instructions = it.next().unwrap();
+        let nodes = it
+            .skip(1)
+            .map(|line| {
+                let (node, rest) = line.split_once(" = ").unwrap();
+                let (left, right) = rest.trim_matches(['(', ')']).split_once(", ").unwrap();
+                (node.to_string(), (left.to_string(), right.to_string()))
+            })
+            .collect();
+        (instructions, nodes)
+    }
+
+    pub fn run1(input: &str) -> usize {
+        let (instructions, nodes) = parse(input);
+        let nodes = nodes.into_iter().collect::<BTreeMap<_, _>>();
+        let mut node = "AAA";
+        for (steps, d) in instructions.chars().cycle().enumerate() {
+            let next = &nodes[node];
+            node = if d == 'L' { &next.0 } else { &next.1 };
+            if node == "ZZZ" {
+                return steps + 1;
+            }
+        }
+        unreachable!()
+    }
+
+    pub fn run2(input: &str) -> u64 {
+        let (instructions, nodes) = parse(input);
+        let nodes = nodes.into_iter().collect::<HashMap<_, _>>();
+        let ghosts = nodes
+            .keys()
+            .filter(|node| node.ends_with('A'))
+            .map(|start| {
+                GhostCycle::analyse(
+                    start.as_str(),
+                    instructions.as_bytes(),
+                    |node, d| {
+                        let next = &nodes[node];
+                        if d == b'L' {
+                            next.0.as_str()
+                        } else {
+                            next.1.as_str()
+                        }
+                    },
+                    |node| node.ends_with('Z'),
+                )
+            })
+            .collect::<Vec<_>>();
+        first_common_hit(&ghosts).unwrap()
+    }
 }
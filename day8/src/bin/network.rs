@@ -0,0 +1,64 @@
+use day8::network::Network;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    let network = Network::parse(input).unwrap();
+    print!(
+        "{}",
+        network.to_dot(|node| node.ends_with('A'), |node| node.ends_with('Z'))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let input = include_str!("../../example.txt");
+        let network = Network::parse(input).unwrap();
+        assert_eq!(network.len(), 7);
+        assert_eq!(network.instructions, b"RL");
+
+        let aaa = network.id("AAA").unwrap();
+        assert_eq!(network.name(network.step(aaa, b'L')), "BBB");
+        assert_eq!(network.name(network.step(aaa, b'R')), "CCC");
+        assert_eq!(network.steps_to_goal(aaa, |n| n == "ZZZ"), Some(2));
+        assert_eq!(network.steps_to_goal(aaa, |n| n == "EEE"), None);
+
+        let dot = network.to_dot(|n| n == "AAA", |n| n == "ZZZ");
+        assert!(dot.starts_with("digraph network {\n    \"AAA\" [shape=box, color=green];\n"));
+        assert!(dot.contains(
+            "    \"AAA\" -> \"BBB\" [label=\"L\"];\n    \"AAA\" -> \"CCC\" [label=\"R\"];\n"
+        ));
+        assert!(dot.contains(
+            "    \"ZZZ\" [shape=doublecircle, color=red];\n    \"ZZZ\" -> \"ZZZ\" [label=\"LR\"];\n"
+        ));
+    }
+
+    #[test]
+    fn test_dot_quotes_ids() {
+        // Names starting with a digit are not valid bare Graphviz IDs
+        let network = Network::parse(include_str!("../../example3.txt")).unwrap();
+        let dot = network.to_dot(|n| n.ends_with('A'), |n| n.ends_with('Z'));
+        assert!(dot.contains("    \"11A\" [shape=box, color=green];\n"));
+        assert!(dot.contains("    \"11A\" -> \"11B\" [label=\"L\"];\n"));
+        assert!(dot.contains("    \"22B\" -> \"22C\" [label=\"LR\"];\n"));
+        assert!(dot.contains("    \"22Z\" [shape=doublecircle, color=red];\n"));
+        for line in dot.lines().skip(1).filter(|l| *l != "}") {
+            let line = line.trim_start();
+            assert!(line.starts_with('"'), "unquoted ID in {:?}", line);
+            if let Some((_, to)) = line.split_once(" -> ") {
+                assert!(to.starts_with('"'), "unquoted ID in {:?}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_networks() {
+        assert!(Network::parse("LR\n\nAAA = (BBB, BBB)\n").is_err());
+        assert!(Network::parse("LX\n\nAAA = (AAA, AAA)\n").is_err());
+        assert!(Network::parse("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)\n").is_err());
+        assert!(Network::parse("L\n\nAAA = AAA\n").is_err());
+    }
+}
@@ -9,6 +9,7 @@ pub fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day8::ghost::{first_common_hit, GhostCycle};
 
     #[test]
     fn test_example_input() {
@@ -16,4 +17,70 @@ mod tests {
         let result = run(input).unwrap();
         assert_eq!(result, 6);
     }
+
+    #[test]
+    fn test_offsets_and_multiple_hits() {
+        // 11A enters the loop 11B -> 11Z -> 11C after one step and hits at
+        // 2, 5, 8, ... while 22A hits on every odd step from 1.
+        let nodes = "11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n11C = (11B, 11B)\n\
+                     22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22Z, 22Z)\n";
+        assert_eq!(run(&format!("L\n\n{}", nodes)), Ok(5));
+
+        // 33A only ever hits once, before settling into a loop without goals.
+        let nodes = "22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22Z, 22Z)\n\
+                     33A = (33Z, 33Z)\n33Z = (33B, 33B)\n33B = (33B, 33B)\n";
+        assert_eq!(run(&format!("L\n\n{}", nodes)), Ok(1));
+
+        let nodes = "11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n11C = (11B, 11B)\n\
+                     33A = (33Z, 33Z)\n33Z = (33B, 33B)\n33B = (33B, 33B)\n";
+        assert!(run(&format!("L\n\n{}", nodes)).is_err());
+
+        // Goals at several points of the loop, at different instruction phases.
+        let nodes = "44A = (44C, 44Z)\n44B = (44A, 44B)\n44C = (44B, 44C)\n44Y = (44A, 44Z)\n\
+                     44Z = (44B, 44Z)\n55A = (55Z, 55Y)\n55B = (55A, 55Y)\n55Z = (55Y, 55Z)\n\
+                     55Y = (55A, 55A)\n";
+        assert_eq!(brute_force("RRL", nodes), 13);
+        assert_eq!(run(&format!("RRL\n\n{}", nodes)), Ok(13));
+    }
+
+    #[test]
+    fn test_start_goal_on_its_own_loop() {
+        // 0 -> 1 -> 0 with 0 the goal: the start is on the loop, so it is hit
+        // again at 2, 4, ... even though step 0 itself doesn't count
+        let ghost = GhostCycle::analyse(0u8, b"L", |node, _| 1 - node, |node| node == 0);
+        assert_eq!(ghost.prefix, 0);
+        assert_eq!(ghost.cycle_length, 2);
+        assert_eq!(ghost.cycle_hits, vec![0]);
+        assert!(ghost.hits_at(2));
+        assert!(!ghost.hits_at(3));
+        assert_eq!(first_common_hit(std::slice::from_ref(&ghost)), Some(2));
+
+        // Together with a ghost that hits on every odd step they never meet,
+        // and with one that hits every third step they meet at 6
+        let odd = GhostCycle::analyse(0u8, b"L", |node, _| 1 - node, |node| node == 1);
+        assert_eq!(first_common_hit(&[ghost.clone(), odd]), None);
+        let third = GhostCycle::analyse(0u8, b"L", |node, _| (node + 1) % 3, |node| node == 0);
+        assert_eq!(first_common_hit(&[ghost, third]), Some(6));
+    }
+
+    fn brute_force(instructions: &str, nodes: &str) -> usize {
+        let map = nodes
+            .lines()
+            .map(|l| (&l[0..3], (&l[7..10], &l[12..15])))
+            .collect::<std::collections::HashMap<_, _>>();
+        let mut ghosts = map
+            .keys()
+            .filter(|n| n.ends_with('A'))
+            .copied()
+            .collect::<Vec<_>>();
+        for (steps, d) in instructions.chars().cycle().enumerate().take(10_000) {
+            for g in ghosts.iter_mut() {
+                *g = if d == 'L' { map[g].0 } else { map[g].1 };
+            }
+            if ghosts.iter().all(|g| g.ends_with('Z')) {
+                return steps + 1;
+            }
+        }
+        panic!("No common hit found");
+    }
 }
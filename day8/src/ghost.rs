@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where one ghost hits a goal. The walk is determined by the pair
+// (node, instruction index), so after `prefix` steps it enters a loop of
+// `cycle_length` steps that it repeats forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub prefix: u64,
+    pub cycle_length: u64,
+    // Goal hits before the loop starts, as step counts. Step 0 counts when
+    // the start is a goal, so that it repeats if the start is on the loop.
+    pub prefix_hits: Vec<u64>,
+    // Goal hits during the first pass of the loop. Each one repeats every
+    // `cycle_length` steps after that.
+    pub cycle_hits: Vec<u64>,
+}
+
+impl GhostCycle {
+    pub fn analyse<S: Copy + Eq + Hash>(
+        start: S,
+        instructions: &[u8],
+        step: impl Fn(S, u8) -> S,
+        is_goal: impl Fn(S) -> bool,
+    ) -> GhostCycle {
+        let mut seen: HashMap<(S, usize), u64> = HashMap::new();
+        Self::walk(start, instructions, step, is_goal, |node, index, steps| {
+            seen.insert((node, index), steps)
+        })
+    }
+
+    // Same as `analyse`, but for nodes numbered 0..node_count, which lets
+    // the visited states live in a flat table instead of a hash map.
+    pub fn analyse_dense(
+        start: u16,
+        node_count: usize,
+        instructions: &[u8],
+        step: impl Fn(u16, u8) -> u16,
+        is_goal: impl Fn(u16) -> bool,
+    ) -> GhostCycle {
+        let mut seen = vec![u64::MAX; node_count * instructions.len()];
+        Self::walk(start, instructions, step, is_goal, |node, index, steps| {
+            let slot = &mut seen[node as usize * instructions.len() + index];
+            let first = (*slot != u64::MAX).then_some(*slot);
+            *slot = steps;
+            first
+        })
+    }
+
+    // `visit` records the step count for a state and returns the step count
+    // it was first seen at, if it was.
+    fn walk<S: Copy>(
+        start: S,
+        instructions: &[u8],
+        step: impl Fn(S, u8) -> S,
+        is_goal: impl Fn(S) -> bool,
+        mut visit: impl FnMut(S, usize, u64) -> Option<u64>,
+    ) -> GhostCycle {
+        let mut hits = vec![];
+        let mut node = start;
+        let mut steps = 0u64;
+
+        loop {
+            let index = steps as usize % instructions.len();
+            if let Some(first) = visit(node, index, steps) {
+                let (prefix_hits, cycle_hits) = hits.into_iter().partition(|&h| h < first);
+                return GhostCycle {
+                    prefix: first,
+                    cycle_length: steps - first,
+                    prefix_hits,
+                    cycle_hits,
+                };
+            }
+            if is_goal(node) {
+                hits.push(steps);
+            }
+            node = step(node, instructions[index]);
+            steps += 1;
+        }
+    }
+
+    pub fn hits_at(&self, steps: u64) -> bool {
+        if steps < self.prefix {
+            self.prefix_hits.contains(&steps)
+        } else {
+            let offset = (steps - self.prefix) % self.cycle_length + self.prefix;
+            self.cycle_hits.contains(&offset)
+        }
+    }
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+// Solves x = a (mod n), x = b (mod m) for moduli that need not be coprime.
+// Returns the combined residue and modulus, or None if there's no solution
+// or the modulus outgrows an i128.
+pub fn crt(a: i128, n: i128, b: i128, m: i128) -> Option<(i128, i128)> {
+    let (g, p, _) = extended_gcd(n, m);
+    if (b - a) % g != 0 {
+        return None;
+    }
+    let lcm = (n / g).checked_mul(m)?;
+    let k = ((b - a) / g % (m / g)).checked_mul(p % (m / g))? % (m / g);
+    let x = (a + n.checked_mul(k)?).rem_euclid(lcm);
+    Some((x, lcm))
+}
+
+// The first step count where every ghost is on a goal at the same time.
+pub fn first_common_hit(ghosts: &[GhostCycle]) -> Option<u64> {
+    let longest = ghosts.iter().max_by_key(|g| g.prefix)?;
+
+    // Before the longest prefix ends, only that ghost's prefix hits can work.
+    // Ghosts sitting on goals before they have moved don't count, so the
+    // search starts at step 1.
+    if let Some(&hit) = longest
+        .prefix_hits
+        .iter()
+        .find(|&&h| h > 0 && ghosts.iter().all(|g| g.hits_at(h)))
+    {
+        return Some(hit);
+    }
+
+    // After that every ghost is looping, so each one pins the answer to one
+    // of its cycle hits modulo its cycle length.
+    let mut residues: Vec<(i128, i128)> = vec![(0, 1)];
+    for ghost in ghosts {
+        let n = ghost.cycle_length as i128;
+        let mut combined = ghost
+            .cycle_hits
+            .iter()
+            .flat_map(|&h| {
+                residues
+                    .iter()
+                    .filter_map(move |&(a, m)| crt(a, m, h as i128 % n, n))
+            })
+            .collect::<Vec<_>>();
+        combined.sort();
+        combined.dedup();
+        residues = combined;
+    }
+
+    let limit = longest.prefix.max(1) as i128;
+    residues
+        .into_iter()
+        .map(|(a, m)| {
+            if a >= limit {
+                a
+            } else {
+                a + (limit - a + m - 1) / m * m
+            }
+        })
+        .min()
+        .and_then(|x| u64::try_from(x).ok())
+}
@@ -1,2 +1,4 @@
+pub mod ghost;
+pub mod network;
 pub mod part1;
 pub mod part2;
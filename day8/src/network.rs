@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+// The node map as an arena. Node names are interned into u16 ids once at
+// parse time, after which walking the network is just indexing into the
+// `left` and `right` arrays.
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub instructions: Vec<u8>,
+    names: Vec<String>,
+    ids: HashMap<String, u16>,
+    left: Vec<u16>,
+    right: Vec<u16>,
+}
+
+impl Network {
+    const PARENTHESIS: &[char] = &['(', ')'];
+
+    pub fn parse(input: &str) -> Result<Network, String> {
+        let mut it = input.lines();
+        let instructions = it
+            .next()
+            .ok_or("Expected an instruction line")?
+            .bytes()
+            .collect::<Vec<u8>>();
+        if instructions.is_empty() {
+            return Err("Expected at least one instruction".to_string());
+        }
+        if let Some(c) = instructions.iter().find(|&&c| c != b'L' && c != b'R') {
+            return Err(format!("Unknown instruction {}", *c as char));
+        }
+
+        // Takes in "AAA = (BBB, CCC)"
+        let lines = it
+            .filter(|l| !l.trim().is_empty())
+            .map(|line| {
+                let (node, rest) = line.split_once(" = ")?;
+                let (left, right) = rest.trim_matches(Self::PARENTHESIS).split_once(", ")?;
+                Some((node, left, right))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Invalid node line")?;
+
+        if lines.len() > u16::MAX as usize {
+            return Err("Too many nodes".to_string());
+        }
+        let mut ids = HashMap::new();
+        for (id, (node, _, _)) in lines.iter().enumerate() {
+            if ids.insert(node.to_string(), id as u16).is_some() {
+                return Err(format!("Node {} is defined twice", node));
+            }
+        }
+        let lookup = |name: &str| {
+            ids.get(name)
+                .copied()
+                .ok_or_else(|| format!("Node {} is never defined", name))
+        };
+
+        Ok(Network {
+            instructions,
+            names: lines.iter().map(|(node, _, _)| node.to_string()).collect(),
+            left: lines
+                .iter()
+                .map(|(_, left, _)| lookup(left))
+                .collect::<Result<_, _>>()?,
+            right: lines
+                .iter()
+                .map(|(_, _, right)| lookup(right))
+                .collect::<Result<_, _>>()?,
+            ids,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<u16> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u16) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn step(&self, id: u16, direction: u8) -> u16 {
+        if direction == b'L' {
+            self.left[id as usize]
+        } else {
+            self.right[id as usize]
+        }
+    }
+
+    // Runs the predicate once per node so walks can check a flag instead.
+    pub fn select(&self, predicate: impl Fn(&str) -> bool) -> Vec<bool> {
+        self.names.iter().map(|n| predicate(n)).collect()
+    }
+
+    pub fn nodes_where(&self, predicate: impl Fn(&str) -> bool) -> Vec<u16> {
+        (0..self.len() as u16)
+            .filter(|&id| predicate(self.name(id)))
+            .collect()
+    }
+
+    // Steps from `start` until a goal node is reached. Gives up once every
+    // (node, instruction) state has been seen, as the walk is looping then.
+    pub fn steps_to_goal(&self, start: u16, goal: impl Fn(&str) -> bool) -> Option<usize> {
+        let goals = self.select(goal);
+        let mut node = start;
+        for (steps, &direction) in self
+            .instructions
+            .iter()
+            .cycle()
+            .take(self.len() * self.instructions.len())
+            .enumerate()
+        {
+            node = self.step(node, direction);
+            if goals[node as usize] {
+                return Some(steps + 1);
+            }
+        }
+        None
+    }
+
+    pub fn to_dot(&self, start: impl Fn(&str) -> bool, goal: impl Fn(&str) -> bool) -> String {
+        let mut out = String::from("digraph network {\n");
+        for (id, name) in self.names.iter().enumerate() {
+            let style = match (start(name), goal(name)) {
+                (true, _) => " [shape=box, color=green]",
+                (_, true) => " [shape=doublecircle, color=red]",
+                _ => "",
+            };
+            out.push_str(&format!("    \"{}\"{};\n", name, style));
+            let (left, right) = (self.left[id], self.right[id]);
+            if left == right {
+                out.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"LR\"];\n",
+                    name,
+                    self.name(left)
+                ));
+            } else {
+                out.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"L\"];\n",
+                    name,
+                    self.name(left)
+                ));
+                out.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"R\"];\n",
+                    name,
+                    self.name(right)
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
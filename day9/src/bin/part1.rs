@@ -16,4 +16,39 @@ mod tests {
         let result = run(input).unwrap();
         assert_eq!(result, 114);
     }
+
+    #[test]
+    fn test_arbitrary_positions() {
+        use day9::sequence::Sequence;
+
+        let sequence = Sequence::parse("10 13 16 21 30 45").unwrap();
+        assert_eq!(sequence.degree(), Some(3));
+        assert_eq!(sequence.value_at(6), Some(68));
+        assert_eq!(sequence.value_at(-1), Some(5));
+        assert_eq!(sequence.value_at(3), Some(21));
+
+        // n^2 + 1 sampled from 0.
+        let squares = Sequence::parse("1 2 5 10 17").unwrap();
+        assert_eq!(squares.value_at(1000), Some(1_000_001));
+        assert_eq!(squares.value_at(-1000), Some(1_000_001));
+        assert_eq!(
+            squares.value_at(3_000_000_000_000_000_000),
+            Some(9_000_000_000_000_000_000_000_000_000_000_000_001)
+        );
+        assert_eq!(squares.value_at(i128::MAX / 2), None);
+
+        let zeros = Sequence::parse("0 0 0").unwrap();
+        assert_eq!(zeros.degree(), None);
+        assert_eq!(zeros.value_at(-7), Some(0));
+    }
+
+    #[test]
+    fn test_non_polynomial() {
+        use day9::sequence::Sequence;
+
+        assert!(Sequence::parse("1 2 4 8").is_err());
+        assert!(Sequence::parse("5").is_err());
+        assert!(Sequence::parse("").is_err());
+        assert!(run("0 3 6 9\n1 2 4 8\n").is_err());
+    }
 }
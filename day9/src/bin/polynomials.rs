@@ -0,0 +1,73 @@
+use day9::polynomial::Fit;
+
+pub fn main() {
+    let input = include_str!("../../input.txt");
+    for (n, line) in input.lines().enumerate() {
+        match Fit::parse(line) {
+            Ok(fit) => println!(
+                "{:>4}: degree {:>2}, {}: {}",
+                n + 1,
+                fit.degree().map_or("-".to_string(), |d| d.to_string()),
+                if fit.is_exact() { "exact" } else { "INEXACT" },
+                fit.polynomial
+            ),
+            Err(e) => println!("{:>4}: {}", n + 1, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let input = include_str!("../../example.txt");
+        let fits = input
+            .lines()
+            .map(|line| Fit::parse(line).unwrap())
+            .collect::<Vec<_>>();
+        let printed = fits
+            .iter()
+            .map(|f| f.polynomial.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            printed,
+            vec!["3 x", "1/2 x^2 + 3/2 x + 1", "1/3 x^3 - x^2 + 11/3 x + 10"]
+        );
+        assert_eq!(
+            fits.iter().map(|f| f.degree()).collect::<Vec<_>>(),
+            vec![Some(1), Some(2), Some(3)]
+        );
+        assert!(fits.iter().all(|f| f.is_exact()));
+    }
+
+    #[test]
+    fn test_formatting() {
+        let printed = |line| Fit::parse(line).unwrap().polynomial.to_string();
+        assert_eq!(printed("0 0 0"), "0");
+        assert_eq!(printed("-4 -4"), "-4");
+        assert_eq!(printed("7 7 10 16 25"), "3/2 x^2 - 3/2 x + 7");
+        assert_eq!(printed("0 -1 -4 -9"), "-x^2");
+    }
+
+    #[test]
+    fn test_matches_extrapolation() {
+        use day9::sequence::Sequence;
+
+        let input = include_str!("../../input.txt");
+        for line in input.lines() {
+            let fit = Fit::parse(line).unwrap();
+            let sequence = Sequence::parse(line).unwrap();
+            assert!(fit.is_exact());
+            for x in [-3, -1, 25, 100] {
+                assert_eq!(
+                    fit.polynomial.evaluate(x),
+                    sequence
+                        .value_at(x)
+                        .map(day9::polynomial::Rational::integer)
+                );
+            }
+        }
+    }
+}
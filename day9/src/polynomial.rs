@@ -0,0 +1,218 @@
+use crate::sequence::Sequence;
+use std::cmp::Ordering;
+use std::fmt;
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    pub numerator: i128,
+    pub denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let g = gcd(numerator, denominator);
+        let sign = denominator.signum();
+        Some(Rational {
+            numerator: sign.checked_mul(numerator / g)?,
+            denominator: sign.checked_mul(denominator / g)?,
+        })
+    }
+
+    pub fn integer(n: i128) -> Rational {
+        Rational {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let g = gcd(self.denominator, other.denominator);
+        let denominator = (self.denominator / g).checked_mul(other.denominator)?;
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator / g)?
+            .checked_add(other.numerator.checked_mul(self.denominator / g)?)?;
+        Rational::new(numerator, denominator)
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(Rational::new(
+            other.numerator.checked_neg()?,
+            other.denominator,
+        )?)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // Cross-cancel first to keep the intermediate products small.
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        Rational::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        )
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            d => write!(f, "{}/{}", self.numerator, d),
+        }
+    }
+}
+
+// Coefficients in increasing powers of x, where x is the position in the
+// history, starting from 0. Trailing zero coefficients are never stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    pub coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    // Expands the Newton form sum of d_j * C(x, j) into powers of x.
+    pub fn from_sequence(sequence: &Sequence) -> Option<Polynomial> {
+        let mut coefficients = vec![Rational::ZERO; sequence.differences().len()];
+        // x (x - 1) ... (x - j + 1), in increasing powers of x.
+        let mut falling: Vec<i128> = vec![1];
+        let mut factorial: i128 = 1;
+
+        for (j, &d) in sequence.differences().iter().enumerate() {
+            if j > 0 {
+                let j = j as i128;
+                let mut next: Vec<i128> = vec![0; falling.len() + 1];
+                for (power, &c) in falling.iter().enumerate() {
+                    next[power + 1] = next[power + 1].checked_add(c)?;
+                    next[power] = next[power].checked_sub(c.checked_mul(j - 1)?)?;
+                }
+                falling = next;
+                factorial = factorial.checked_mul(j)?;
+            }
+            let scale = Rational::new(d, factorial)?;
+            for (power, &c) in falling.iter().enumerate() {
+                let term = scale.checked_mul(Rational::integer(c))?;
+                coefficients[power] = coefficients[power].checked_add(term)?;
+            }
+        }
+
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+        Some(Polynomial { coefficients })
+    }
+
+    // None for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn evaluate(&self, x: i128) -> Option<Rational> {
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(Rational::ZERO, |acc, &c| {
+                acc.checked_mul(Rational::integer(x))?.checked_add(c)
+            })
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for (power, c) in self.coefficients.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            let sign = match (c.numerator.cmp(&0), first) {
+                (Ordering::Less, true) => "-",
+                (Ordering::Less, false) => " - ",
+                (_, true) => "",
+                (_, false) => " + ",
+            };
+            let magnitude = Rational {
+                numerator: c.numerator.abs(),
+                denominator: c.denominator,
+            };
+            let coefficient = match (magnitude.numerator, magnitude.denominator, power) {
+                (1, 1, 1..) => String::new(),
+                _ if power == 0 => magnitude.to_string(),
+                _ => format!("{} ", magnitude),
+            };
+            let variable = match power {
+                0 => String::new(),
+                1 => "x".to_string(),
+                p => format!("x^{}", p),
+            };
+            write!(f, "{}{}{}", sign, coefficient, variable)?;
+            first = false;
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fit {
+    pub polynomial: Polynomial,
+    // The history minus the polynomial at each position, all zero when the
+    // polynomial reproduces the history.
+    pub residuals: Vec<Rational>,
+}
+
+impl Fit {
+    pub fn new(history: &[i128]) -> Result<Fit, String> {
+        let sequence = Sequence::new(history)?;
+        let polynomial = Polynomial::from_sequence(&sequence)
+            .ok_or("Polynomial coefficients overflowed i128")?;
+        let residuals = history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| Rational::integer(y).checked_sub(polynomial.evaluate(x as i128)?))
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Residuals overflowed i128")?;
+        Ok(Fit {
+            polynomial,
+            residuals,
+        })
+    }
+
+    pub fn parse(input: &str) -> Result<Fit, String> {
+        let history = input
+            .split_whitespace()
+            .map(|n| n.parse::<i128>())
+            .collect::<Result<Vec<i128>, _>>()
+            .map_err(|e| format!("Parse number failed: {}", e))?;
+        Self::new(&history)
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.polynomial.degree()
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.residuals.iter().all(|r| r.is_zero())
+    }
+}
@@ -0,0 +1,67 @@
+// A history stored as its Newton forward differences: `differences[j]` is
+// the first entry of the j-th difference row. A sequence of degree d has d + 1
+// of them, and the value at any integer position k is
+// sum over j of C(k, j) * differences[j].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    differences: Vec<i128>,
+}
+
+impl Sequence {
+    pub fn parse(input: &str) -> Result<Sequence, String> {
+        let history = input
+            .split_whitespace()
+            .map(|n| n.parse::<i128>())
+            .collect::<Result<Vec<i128>, _>>()
+            .map_err(|e| format!("Parse number failed: {}", e))?;
+        Self::new(&history)
+    }
+
+    // Fails unless some difference row comes out all zero, as otherwise the
+    // history is too short to pin down a polynomial.
+    pub fn new(history: &[i128]) -> Result<Sequence, String> {
+        let mut row = history.to_vec();
+        let mut differences = vec![];
+        while !row.is_empty() {
+            if row.iter().all(|&n| n == 0) {
+                return Ok(Sequence { differences });
+            }
+            differences.push(row[0]);
+            for i in 0..row.len() - 1 {
+                row[i] = row[i + 1]
+                    .checked_sub(row[i])
+                    .ok_or("Difference overflowed i128")?;
+            }
+            row.pop();
+        }
+        Err(format!(
+            "History {:?} is not polynomial, no difference row is all zero",
+            history
+        ))
+    }
+
+    pub fn differences(&self) -> &[i128] {
+        &self.differences
+    }
+
+    // None for the all-zero sequence.
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    // The value at position k, where the first entry of the history is at 0.
+    // None if the result or an intermediate step doesn't fit in an i128.
+    pub fn value_at(&self, k: i128) -> Option<i128> {
+        let mut binomial: i128 = 1;
+        let mut value: i128 = 0;
+        for (j, &d) in self.differences.iter().enumerate() {
+            if j > 0 {
+                // C(k, j) = C(k, j - 1) * (k - j + 1) / j, exact at every step.
+                let j = j as i128;
+                binomial = binomial.checked_mul(k.checked_sub(j - 1)?)? / j;
+            }
+            value = value.checked_add(binomial.checked_mul(d)?)?;
+        }
+        Some(value)
+    }
+}